@@ -1,22 +1,44 @@
 //! Challenge 1 - Multiples of 3 and 5
+//! Tags: brute-force
+//! Difficulty: 5%
 //!
 //! If we list all the natural numbers below 10 that are multiples of 3 or 5,
 //! we get 3, 5, 6 and 9. The sum of these multiples is 23.
 //!
 //! Find the sum of all the multiples of 3 or 5 below 1000.
 
+extern crate euler;
+
+use euler::solution::{self, Answer, Solution};
+
+
+/// Sum every multiple of 3 or 5 below 1000.
+pub struct MultiplesOf3And5;
+
+impl Solution for MultiplesOf3And5 {
+    fn number(&self) -> usize {
+        1
+    }
+
+    fn title(&self) -> &str {
+        "Multiples of 3 and 5"
+    }
+
+    fn solve(&self) -> Answer {
+        let sum = (1..1000)
+            .filter_map(|i| {
+                if i % 3 == 0 || i % 5 == 0 {
+                    Some(i)
+                } else {
+                    None
+                }
+            })
+            .fold(0, |acc, i| acc + i);
+
+        Answer::from(sum)
+    }
+}
 
 fn main() {
-    let sum = (1..1000)
-        .filter_map(|i| {
-            if i % 3 == 0 || i % 5 == 0 {
-                Some(i)
-            } else {
-                None
-            }
-        })
-        .fold(0, |acc, i| acc + i);
-    println!("{}", sum);
-
-    assert_eq!(sum, 233168);
+    solution::run(&MultiplesOf3And5);
 }
@@ -1,4 +1,6 @@
 //! Challenge 10 - Summation of Primes
+//! Tags: primes
+//! Difficulty: 5%
 //!
 //! The sum of the primes below 10 is 2 + 3 + 5 + 7 = 17.
 //!
@@ -6,12 +8,30 @@
 
 extern crate euler;
 
+use euler::solution::{self, Answer, Solution};
+
+
+/// Sum every prime below two million.
+pub struct SummationOfPrimes;
+
+impl Solution for SummationOfPrimes {
+    fn number(&self) -> usize {
+        10
+    }
+
+    fn title(&self) -> &str {
+        "Summation of Primes"
+    }
+
+    fn solve(&self) -> Answer {
+        let n = 2_000_000;
+        let sum = prime_sum(n);
+        Answer::from(sum)
+    }
+}
 
 fn main() {
-    let n = 2_000_000;
-    let sum = prime_sum(n);
-    assert_eq!(sum, 142913828922);
-    println!("{}", sum);
+    solution::run(&SummationOfPrimes);
 }
 
 fn prime_sum(n: usize) -> usize {
@@ -1,4 +1,6 @@
 //! Challenge 12 - Highly Divisible Triangle Numbers
+//! Tags: divisors
+//! Difficulty: 5%
 //!
 //! The sequence of triangle numbers is generated by adding the natural
 //! numbers. So the 7th triangle number would be 1 + 2 + 3 + 4 + 5 + 6 + 7
@@ -20,6 +22,31 @@
 //! 28: 1,2,4,7,14,28
 //! We can see that 28 is the first triangle number to have over five divisors.
 
+extern crate euler;
+
+use euler::solution::{self, Answer, Solution};
+
+/// Find the first triangle number with over 500 divisors.
+pub struct HighlyDivisibleTriangleNumbers;
+
+impl Solution for HighlyDivisibleTriangleNumbers {
+    fn number(&self) -> usize {
+        12
+    }
+
+    fn title(&self) -> &str {
+        "Highly Divisible Triangle Numbers"
+    }
+
+    fn is_implemented(&self) -> bool {
+        false
+    }
+
+    fn solve(&self) -> Answer {
+        unimplemented!()
+    }
+}
+
 fn main() {
-    unimplemented!()
+    solution::run(&HighlyDivisibleTriangleNumbers);
 }
@@ -1,4 +1,6 @@
 //! Challenge 13 - Large Sum
+//! Tags: big-numbers, data
+//! Difficulty: 5%
 //!
 //! Work out the first ten digits of the sum of the following one-hundred
 //! 50-digit numbers.
@@ -104,44 +106,42 @@
 //! 20849603980134001723930671666823555245252804609722
 //! 53503534226472524250874054075591789781264330331690
 
-#[macro_use]
-extern crate log;
 extern crate euler;
 extern crate num;
 
-use std::fs::File;
-use std::path::Path;
-use std::io::Read;
-
+use euler::data;
+use euler::solution::{self, Answer, Solution};
 use num::{BigUint, Zero};
 
 
-fn main() {
-    let filename = format!("{}/data/problem_13_numbers.txt", env!("CARGO_MANIFEST_DIR"));
-    let numbers = parse_numbers(&filename);
+/// Add up one hundred 50-digit numbers.
+pub struct LargeSum;
 
-    // Double check the parsing was successful
-    let should_be: BigUint = "37107287533902102798797998220837590246510135740250".parse().unwrap();
-    assert_eq!(numbers[0], should_be);
+impl Solution for LargeSum {
+    fn number(&self) -> usize {
+        13
+    }
 
-    let total: BigUint = numbers.iter().fold(Zero::zero(), |acc, elem| acc + elem);
-    let as_str = format!("{}", total);
-    let substr = as_str.split_at(10).0;
-    assert_eq!("5537376230", substr);
-    println!("{}", substr);
-}
+    fn title(&self) -> &str {
+        "Large Sum"
+    }
 
-pub fn parse_numbers(filename: &str) -> Vec<BigUint> {
-    let path = Path::new(filename);
-    debug!("Reading from {}", path.display());
-    let mut f = File::open(&path).expect("Unable to open file");
+    fn solve(&self) -> Answer {
+        let numbers: Vec<BigUint> = data::load(13).unwrap();
 
-    let mut s = String::new();
-    f.read_to_string(&mut s).expect("Couldn't read to string");
+        // Double check the parsing was successful
+        let should_be: BigUint = "37107287533902102798797998220837590246510135740250"
+            .parse()
+            .unwrap();
+        assert_eq!(numbers[0], should_be);
 
-    let mut numbers: Vec<BigUint> = vec![];
-    for line in s.lines() {
-        numbers.push(line.trim().parse().unwrap());
+        let total: BigUint = numbers.iter().fold(Zero::zero(), |acc, elem| acc + elem);
+        let as_str = format!("{}", total);
+        let substr = as_str.split_at(10).0;
+        Answer::from(substr)
     }
-    numbers
+}
+
+fn main() {
+    solution::run(&LargeSum);
 }
@@ -1,4 +1,6 @@
 //! Challenge 2 - Even Fibonacci numbers
+//! Tags: fibonacci
+//! Difficulty: 5%
 //!
 //! Each new term in the Fibonacci sequence is generated by adding the
 //! previous two terms. By starting with 1 and 2, the first 10 terms will be:
@@ -12,17 +14,32 @@ extern crate euler;
 
 use std::iter::Iterator;
 use euler::Fibonacci;
+use euler::solution::{self, Answer, Solution};
 
 
-fn main() {
-    let fib = Fibonacci::new();
+/// Sum the even fibonacci numbers below four million.
+pub struct EvenFibonacciNumbers;
+
+impl Solution for EvenFibonacciNumbers {
+    fn number(&self) -> usize {
+        2
+    }
+
+    fn title(&self) -> &str {
+        "Even Fibonacci numbers"
+    }
 
-    let sum = fib.take_while(|i| *i < 4_000_000)
-        .filter(|i| i % 2 == 0)
-        .fold(0, |acc, i| acc + i);
+    fn solve(&self) -> Answer {
+        let fib = Fibonacci::new();
 
-    println!("{}", sum);
+        let sum = fib.take_while(|i| *i < 4_000_000)
+            .filter(|i| i % 2 == 0)
+            .fold(0, |acc, i| acc + i);
 
-    // The solution
-    assert_eq!(sum, 4613732);
+        Answer::from(sum)
+    }
+}
+
+fn main() {
+    solution::run(&EvenFibonacciNumbers);
 }
@@ -1,4 +1,6 @@
 //! Challenge 3 - Largest Prime factor
+//! Tags: primes
+//! Difficulty: 5%
 //!
 //! The prime factors of 13195 are 5, 7, 13 and 29.
 //!
@@ -6,21 +8,37 @@
 
 extern crate euler;
 use euler::primes;
+use euler::solution::{self, Answer, Solution};
 
-fn main() {
-    let n: u64 = 600851475143;
-    let limit = (n as f64).sqrt() as usize;
-    let prime_list = primes(limit);
-
-    let mut factors = vec![];
-    for prime in prime_list {
-        if n % (prime as u64) == 0 {
-            factors.push(prime)
-        }
+/// Find the largest prime factor of 600851475143.
+pub struct LargestPrimeFactor;
+
+impl Solution for LargestPrimeFactor {
+    fn number(&self) -> usize {
+        3
     }
 
-    let biggest_factor = *factors.iter().max().unwrap();
-    println!("{:?}", biggest_factor);
-    assert_eq!(biggest_factor, 6857);
+    fn title(&self) -> &str {
+        "Largest Prime factor"
+    }
+
+    fn solve(&self) -> Answer {
+        let n: u64 = 600851475143;
+        let limit = (n as f64).sqrt() as usize;
+        let prime_list = primes(limit);
 
+        let mut factors = vec![];
+        for prime in prime_list {
+            if n % (prime as u64) == 0 {
+                factors.push(prime)
+            }
+        }
+
+        let biggest_factor = *factors.iter().max().unwrap();
+        Answer::from(biggest_factor)
+    }
+}
+
+fn main() {
+    solution::run(&LargestPrimeFactor);
 }
@@ -1,4 +1,6 @@
 //! Challenge 4 - Largest Palindrome Number
+//! Tags: palindromes
+//! Difficulty: 5%
 //!
 //! A palindromic number reads the same both ways. The largest palindrome
 //! made from the product of two 2-digit numbers is 9009 = 91 × 99.
@@ -7,18 +9,95 @@
 
 extern crate euler;
 use euler::is_palindrome;
+use euler::solution::{self, Answer, Solution};
 
-fn main() {
-    let mut largest_palindrome = 0;
+/// Check the product of every pair of 3-digit numbers.
+pub struct LargestPalindromeProduct;
+
+impl Solution for LargestPalindromeProduct {
+    fn number(&self) -> usize {
+        4
+    }
+
+    fn title(&self) -> &str {
+        "Largest Palindrome Number"
+    }
 
-    for i in 100..1000 {
-        for j in 100..1000 {
-            let res = i * j;
-            if is_palindrome(res) && res > largest_palindrome {
-                largest_palindrome = res;
+    fn name(&self) -> &str {
+        "nested-loops"
+    }
+
+    fn solve(&self) -> Answer {
+        let mut largest_palindrome = 0;
+
+        for i in 100..1000 {
+            for j in 100..1000 {
+                let res = i * j;
+                if is_palindrome(res) && res > largest_palindrome {
+                    largest_palindrome = res;
+                }
             }
         }
+
+        Answer::from(largest_palindrome)
+    }
+}
+
+/// Work down through the 6-digit palindromes, stopping at the first one
+/// which is the product of two 3-digit numbers.
+pub struct PalindromeFirst;
+
+impl Solution for PalindromeFirst {
+    fn number(&self) -> usize {
+        4
+    }
+
+    fn title(&self) -> &str {
+        "Largest Palindrome Number"
+    }
+
+    fn name(&self) -> &str {
+        "palindrome-first"
     }
 
-    println!("{}", largest_palindrome);
+    fn solve(&self) -> Answer {
+        for first_half in (100..1000).rev() {
+            let palindrome = mirror(first_half);
+            if has_3_digit_factors(palindrome) {
+                return Answer::from(palindrome);
+            }
+        }
+
+        // 100 * 1001 = 100100, so there's always a 6-digit answer
+        unreachable!()
+    }
+}
+
+/// Append the digits of `n` to itself, in reverse (e.g. 123 -> 123321).
+fn mirror(n: usize) -> usize {
+    let forwards = n.to_string();
+    let backwards: String = forwards.chars().rev().collect();
+    (forwards + &backwards).parse().unwrap()
+}
+
+fn has_3_digit_factors(n: usize) -> bool {
+    (100..1000).rev().any(|i| {
+        let j = n / i;
+        n % i == 0 && j >= 100 && j < 1000
+    })
+}
+
+fn main() {
+    solution::run(&LargestPalindromeProduct);
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn both_implementations_agree() {
+        assert_eq!(LargestPalindromeProduct.solve(), PalindromeFirst.solve());
+    }
 }
@@ -1,4 +1,6 @@
-//! Problem 5 - Smallest Multiple
+//! Challenge 5 - Smallest Multiple
+//! Tags: lcm
+//! Difficulty: 5%
 //!
 //! 2520 is the smallest number that can be divided by each of the numbers
 //! from 1 to 10 without any remainder.
@@ -10,10 +12,27 @@
 extern crate euler;
 
 use euler::lowest_common_multiple;
+use euler::solution::{self, Answer, Solution};
 
 
+/// Find the lowest common multiple of 1 to 20.
+pub struct SmallestMultiple;
+
+impl Solution for SmallestMultiple {
+    fn number(&self) -> usize {
+        5
+    }
+
+    fn title(&self) -> &str {
+        "Smallest Multiple"
+    }
+
+    fn solve(&self) -> Answer {
+        let smallest = (1...20).fold(1, lowest_common_multiple);
+        Answer::from(smallest)
+    }
+}
+
 fn main() {
-    let smallest = (1...20).fold(1, lowest_common_multiple);
-    assert_eq!(smallest, 232792560);
-    println!("{}", smallest);
+    solution::run(&SmallestMultiple);
 }
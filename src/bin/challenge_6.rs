@@ -1,4 +1,6 @@
 //! Challenge 6 - Sum Square difference
+//! Tags: arithmetic
+//! Difficulty: 5%
 //!
 //! The sum of the squares of the first ten natural numbers is,
 //!
@@ -14,11 +16,31 @@
 
 #![feature(inclusive_range_syntax)]
 
+extern crate euler;
+
+use euler::solution::{self, Answer, Solution};
+
+
+/// Find the sum square difference of the first 100 natural numbers.
+pub struct SumSquareDifference;
+
+impl Solution for SumSquareDifference {
+    fn number(&self) -> usize {
+        6
+    }
+
+    fn title(&self) -> &str {
+        "Sum Square difference"
+    }
+
+    fn solve(&self) -> Answer {
+        let d = sum_square_difference(100);
+        Answer::from(d)
+    }
+}
 
 fn main() {
-    let d = sum_square_difference(100);
-    assert_eq!(d, 25164150);
-    println!("{}", d);
+    solution::run(&SumSquareDifference);
 }
 
 fn sum_square_difference(n: usize) -> usize {
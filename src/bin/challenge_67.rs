@@ -1,4 +1,6 @@
 //! Challenge 67 - Maximum path sum II
+//! Tags: dp, data
+//! Difficulty: 5%
 //!
 //! By starting at the top of the triangle below and moving to adjacent
 //! numbers on the row below, the maximum total from top to bottom is 23.
@@ -14,42 +16,33 @@
 //! and 'Save Link/Target As...'), a 15K text file containing a triangle
 //! with one-hundred rows.
 
-#[macro_use]
-extern crate log;
 extern crate euler;
 extern crate env_logger;
 
-use std::fs::File;
-use std::path::Path;
-use std::io::Read;
+use euler::{data, frontier_reduce};
+use euler::solution::{self, Answer, Solution};
 
-use euler::frontier_reduce;
+/// Find the maximum path through a triangle with one hundred rows.
+pub struct MaximumPathSumII;
 
-fn main() {
-    env_logger::init().unwrap();
-
-    let filename = format!("{}/data/problem_067_triangle.txt",
-                           env!("CARGO_MANIFEST_DIR"));
-    let triangle = parse_triangle(&filename);
-
-    let total = frontier_reduce(triangle);
-    println!("{}", total);
-}
+impl Solution for MaximumPathSumII {
+    fn number(&self) -> usize {
+        67
+    }
 
-fn parse_triangle(filename: &str) -> Vec<Vec<usize>> {
-    let path = Path::new(filename);
-    debug!("Reading from {}", path.display());
-    let mut f = File::open(&path).expect("Unable to open file");
+    fn title(&self) -> &str {
+        "Maximum path sum II"
+    }
 
-    let mut s = String::new();
-    f.read_to_string(&mut s).expect("Couldn't read to string");
+    fn solve(&self) -> Answer {
+        let triangle: Vec<Vec<usize>> = data::load(67).unwrap();
 
-    let mut triangle = vec![];
-    // Iterate over the lines in our string
-    for line in s.lines() {
-        let row: Vec<usize> = line.split(' ').map(|i| i.trim().parse().unwrap()).collect();
-        triangle.push(row);
+        let total = frontier_reduce(triangle);
+        Answer::from(total)
     }
+}
 
-    triangle
+fn main() {
+    env_logger::init().unwrap();
+    solution::run(&MaximumPathSumII);
 }
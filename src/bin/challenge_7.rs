@@ -1,4 +1,6 @@
-//! Problem 7 - 10001st prime
+//! Challenge 7 - 10001st prime
+//! Tags: primes
+//! Difficulty: 5%
 //!
 //! By listing the first six prime numbers: 2, 3, 5, 7, 11, and 13, we can
 //! see that the 6th prime is 13.
@@ -7,12 +9,30 @@
 
 extern crate euler;
 
-fn main() {
-    let primes = euler::primes(1_000_000);
+use euler::solution::{self, Answer, Solution};
+
+/// Find the 10001st prime number.
+pub struct TenThousandAndFirstPrime;
+
+impl Solution for TenThousandAndFirstPrime {
+    fn number(&self) -> usize {
+        7
+    }
+
+    fn title(&self) -> &str {
+        "10001st prime"
+    }
 
-    // take one because "nth" starts from 0
-    let prime = *primes.get(10001 - 1).unwrap();
+    fn solve(&self) -> Answer {
+        let primes = euler::primes(1_000_000);
 
-    assert_eq!(prime, 104743);
-    println!("{}", prime);
+        // take one because "nth" starts from 0
+        let prime = *primes.get(10001 - 1).unwrap();
+
+        Answer::from(prime)
+    }
+}
+
+fn main() {
+    solution::run(&TenThousandAndFirstPrime);
 }
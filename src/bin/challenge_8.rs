@@ -1,4 +1,6 @@
 //! Challenge 8 - Largest Product In A Series
+//! Tags: data
+//! Difficulty: 5%
 //!
 //! The four adjacent digits in the 1000-digit number that have the greatest
 //! product are 9 × 9 × 8 × 9 = 5832.
@@ -27,44 +29,35 @@
 //! 05886116467109405077541002256983155200055935729725
 //! 71636269561882670428252483600823257530420752963450
 
-#![feature(inclusive_range_syntax)]
+extern crate euler;
+extern crate num;
 
-use std::fs::File;
-use std::path::Path;
-use std::io::Read;
+use euler::{data, max_window_product};
+use euler::solution::{self, Answer, Solution};
+use num::ToPrimitive;
 
 
-fn main() {
-    let filename = format!("{}/data/problem_8_number.txt", env!("CARGO_MANIFEST_DIR"));
-    let number = get_number(&filename);
-    let digits: Vec<usize> = number.chars()
-        .filter_map(|s| s.to_digit(10))
-        .map(|i| i as usize)
-        .collect();
+/// Find the 13 adjacent digits with the greatest product.
+pub struct LargestProductInASeries;
 
-    let product = find_product(digits, 13);
-    assert_eq!(product, 23514624000);
-    println!("{}", product);
-}
+impl Solution for LargestProductInASeries {
+    fn number(&self) -> usize {
+        8
+    }
 
-fn get_number(filename: &str) -> String {
-    let path = Path::new(filename);
-    let mut f = File::open(&path).expect("Unable to open file");
-    let mut s = String::new();
-    f.read_to_string(&mut s).expect("Couldn't read to string");
-    s.trim().replace('\n', "")
-}
+    fn title(&self) -> &str {
+        "Largest Product In A Series"
+    }
 
-fn find_product(digits: Vec<usize>, n: usize) -> usize {
-    let mut largest = 0;
+    fn solve(&self) -> Answer {
+        let digits: Vec<u64> = data::load(8).unwrap();
 
-    for i in 0...(digits.len() - n) {
-        let slice = &digits[i..i + n];
-        let prod = slice.iter().product();
-        if prod > largest {
-            largest = prod;
-        }
+        let best = max_window_product(&digits, 13).unwrap();
+        let product = best.product.to_u64().unwrap();
+        Answer::from(product)
     }
+}
 
-    largest
+fn main() {
+    solution::run(&LargestProductInASeries);
 }
@@ -1,4 +1,6 @@
 //! Challenge 9 - Special Pythagorean Triple
+//! Tags: pythagorean-triples
+//! Difficulty: 5%
 //!
 //! A Pythagorean triplet is a set of three natural numbers, a < b < c,
 //! for which,
@@ -12,19 +14,36 @@
 extern crate euler;
 
 use std::iter::Iterator;
+use euler::solution::{self, Answer, Solution};
+
+/// Find the pythagorean triple whose sides add up to 1000.
+pub struct SpecialPythagoreanTriple;
+
+impl Solution for SpecialPythagoreanTriple {
+    fn number(&self) -> usize {
+        9
+    }
+
+    fn title(&self) -> &str {
+        "Special Pythagorean Triple"
+    }
+
+    fn solve(&self) -> Answer {
+        let triples = euler::pythag_triples(1000);
+
+        let specials: Vec<_> = triples.iter()
+            .filter(|&triple| {
+                let &(a, b, c) = triple;
+                a + b + c == 1000
+            })
+            .collect::<Vec<_>>();
+
+        let special = specials.iter().next().unwrap();
+        let product = special.0 * special.1 * special.2;
+        Answer::from(product)
+    }
+}
 
 fn main() {
-    let triples = euler::pythag_triples(1000);
-
-    let specials: Vec<_> = triples.iter()
-        .filter(|&triple| {
-            let &(a, b, c) = triple;
-            a + b + c == 1000
-        })
-        .collect::<Vec<_>>();
-
-    let special = specials.iter().next().unwrap();
-    let product = special.0 * special.1 * special.2;
-    assert_eq!(product, 31875000);
-    println!("{:?}", product);
+    solution::run(&SpecialPythagoreanTriple);
 }
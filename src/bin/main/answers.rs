@@ -0,0 +1,180 @@
+//! The expected answers from `answers.toml`, used to check each solution.
+//!
+//! Answers can be stored in plain text or, to avoid spoilers, as the MD5 or
+//! SHA-256 hash of the answer string.
+
+use std::collections::HashMap;
+
+use md5;
+use toml;
+
+use euler::data::sha256;
+
+
+/// The contents of `answers.toml`, baked in so the runner can check answers
+/// without needing the source tree.
+const ANSWERS: &'static str = include_str!("../../../answers.toml");
+
+
+/// How a solution's answer compared to the expected one.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    /// The answer was what we expected.
+    Correct,
+    /// The answer didn't match the expected one.
+    Wrong {
+        /// The answer we should have gotten, unless it's only stored as a
+        /// hash.
+        expected: Option<String>,
+    },
+    /// There's no expected answer to compare against.
+    Unknown,
+}
+
+
+/// A single expected answer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expected {
+    /// The answer itself.
+    Plain(String),
+    /// The hex-encoded MD5 hash of the answer.
+    Md5(String),
+    /// The hex-encoded SHA-256 hash of the answer.
+    Sha256(String),
+}
+
+impl Expected {
+    /// Does an answer match what we expected?
+    pub fn matches(&self, answer: &str) -> bool {
+        let answer = answer.trim();
+
+        match *self {
+            Expected::Plain(ref expected) => expected == answer,
+            Expected::Md5(ref hash) => {
+                format!("{:x}", md5::compute(answer.as_bytes())) == hash.to_lowercase()
+            }
+            Expected::Sha256(ref hash) => sha256(answer.as_bytes()) == hash.to_lowercase(),
+        }
+    }
+
+    /// Get the expected answer, if it isn't hashed.
+    pub fn plaintext(&self) -> Option<&str> {
+        match *self {
+            Expected::Plain(ref expected) => Some(expected),
+            _ => None,
+        }
+    }
+}
+
+
+/// A lookup table of the expected answer to each problem.
+#[derive(Debug, Clone, Default)]
+pub struct Answers {
+    expected: HashMap<usize, Expected>,
+}
+
+impl Answers {
+    /// Load the answers baked into the runner.
+    pub fn load() -> Answers {
+        Answers::parse(ANSWERS).expect("answers.toml is malformed")
+    }
+
+    /// Parse the contents of an answers file.
+    pub fn parse(src: &str) -> Result<Answers, String> {
+        let value: toml::Value = src.parse().map_err(|e| format!("Invalid TOML ({})", e))?;
+        let table = value.get("answers")
+            .and_then(|v| v.as_table())
+            .ok_or("Expected an [answers] table")?;
+
+        let mut expected = HashMap::new();
+        for (key, value) in table {
+            let number = key.parse()
+                .map_err(|_| format!("{:?} isn't a problem number", key))?;
+            let answer = parse_expected(value)
+                .ok_or_else(|| {
+                    format!("The answer to problem {} should be a string or a table with an \
+                             \"md5\" or \"sha256\" hash",
+                            number)
+                })?;
+            expected.insert(number, answer);
+        }
+
+        Ok(Answers { expected: expected })
+    }
+
+    /// The expected answer to a problem, if we know it.
+    pub fn get(&self, number: usize) -> Option<&Expected> {
+        self.expected.get(&number)
+    }
+
+    /// Check a solution's answer against the expected one.
+    pub fn check(&self, number: usize, answer: &str) -> Verdict {
+        match self.get(number) {
+            Some(expected) if expected.matches(answer) => Verdict::Correct,
+            Some(expected) => {
+                Verdict::Wrong { expected: expected.plaintext().map(|s| s.to_string()) }
+            }
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+
+fn parse_expected(value: &toml::Value) -> Option<Expected> {
+    match *value {
+        toml::Value::String(ref s) => Some(Expected::Plain(s.clone())),
+        toml::Value::Integer(i) => Some(Expected::Plain(i.to_string())),
+        toml::Value::Table(ref table) => {
+            if let Some(hash) = table.get("md5").and_then(|v| v.as_str()) {
+                Some(Expected::Md5(hash.to_string()))
+            } else if let Some(hash) = table.get("sha256").and_then(|v| v.as_str()) {
+                Some(Expected::Sha256(hash.to_string()))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_answers() {
+        let answers = Answers::parse("[answers]\n1 = \"233168\"\n2 = 4613732\n").unwrap();
+
+        assert_eq!(answers.check(1, "233168\n"), Verdict::Correct);
+        assert_eq!(answers.check(2, "4613732"), Verdict::Correct);
+        assert_eq!(answers.check(1, "42"),
+                   Verdict::Wrong { expected: Some(String::from("233168")) });
+        assert_eq!(answers.check(3, "6857"), Verdict::Unknown);
+    }
+
+    #[test]
+    fn check_hashed_answers() {
+        let src = r#"
+            [answers]
+            1 = { md5 = "e1edf9d1967ca96767dcc2b2d6df69f4" }
+            2 = { sha256 = "1F5882E19314AC13ACCA52AD5503184B3CB1FD8DBEEA82E0979D799AF2361704" }
+            "#;
+        let answers = Answers::parse(src).unwrap();
+
+        assert_eq!(answers.check(1, "233168"), Verdict::Correct);
+        assert_eq!(answers.check(1, "233169"), Verdict::Wrong { expected: None });
+        assert_eq!(answers.check(2, "4613732"), Verdict::Correct);
+    }
+
+    #[test]
+    fn answers_need_a_value_or_a_hash() {
+        assert!(Answers::parse("[answers]\n1 = { sha1 = \"abcd\" }\n").is_err());
+    }
+
+    #[test]
+    fn the_bundled_answers_are_valid() {
+        let answers = Answers::load();
+        assert_eq!(answers.check(1, "233168"), Verdict::Correct);
+    }
+}
@@ -0,0 +1,359 @@
+//! Benchmark challenges properly, running each one several times so the
+//! timings can be trusted.
+//!
+//! Challenges are run strictly one after another with a few warm-up runs
+//! first, and the runner can be pinned to a single CPU (any child processes
+//! inherit this) to cut down on noise from the scheduler.
+
+use std::io;
+use std::process::Command;
+
+use ansi_term::Colour::*;
+use time;
+
+use euler::solution::{self, Solution};
+
+use super::{process, solve_in_process, Challenge, Options};
+
+
+/// How a challenge should be benchmarked.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Settings {
+    /// Runs to do (and throw away) before measuring anything.
+    pub warmup: usize,
+    /// The number of measured runs.
+    pub runs: usize,
+}
+
+
+/// The results of benchmarking a single challenge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    pub number: usize,
+    pub name: String,
+    pub outcome: Outcome,
+}
+
+
+/// How benchmarking a challenge went.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Measured(Stats),
+    /// The challenge hasn't been solved yet, so it was skipped.
+    Todo,
+    /// The challenge failed, for this reason.
+    Failed(String),
+}
+
+impl Outcome {
+    /// The name used for this outcome in the history, matching the statuses
+    /// of normal runs.
+    pub fn status(&self) -> &'static str {
+        match *self {
+            Outcome::Measured(_) => "success",
+            Outcome::Todo => "todo",
+            Outcome::Failed(_) => "failed",
+        }
+    }
+}
+
+
+/// Summary statistics for a set of timings, in milliseconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub std_dev: f64,
+    /// The number of samples outside Tukey's fences (more than 1.5 times
+    /// the inter-quartile range away from the middle half).
+    pub outliers: usize,
+}
+
+impl Stats {
+    /// Calculate the statistics for some samples, returning `None` if there
+    /// aren't any.
+    pub fn from_samples(samples: &[f64]) -> Option<Stats> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).expect("Timings are never NaN"));
+
+        let n = sorted.len() as f64;
+        let mean = sorted.iter().sum::<f64>() / n;
+        let variance = if sorted.len() > 1 {
+            sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+
+        let q1 = percentile(&sorted, 0.25);
+        let q3 = percentile(&sorted, 0.75);
+        let iqr = q3 - q1;
+        let (low, high) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        Some(Stats {
+            min: sorted[0],
+            median: percentile(&sorted, 0.5),
+            mean: mean,
+            std_dev: variance.sqrt(),
+            outliers: sorted.iter().filter(|&&x| x < low || x > high).count(),
+        })
+    }
+}
+
+/// Get a percentile from some sorted samples, interpolating between the two
+/// closest ones.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let below = rank.floor() as usize;
+    let above = rank.ceil() as usize;
+
+    sorted[below] + (sorted[above] - sorted[below]) * (rank - below as f64)
+}
+
+
+/// Benchmark some challenge binaries, which should already be compiled.
+pub fn binaries(challenges: &[Challenge], settings: Settings, opts: &Options) -> Vec<Row> {
+    let rows: Vec<Row> = challenges.iter()
+        .map(|challenge| {
+            let path = challenge.binary_path(opts);
+            Row {
+                number: challenge.number,
+                name: challenge.name.clone(),
+                outcome: measure(settings, || run_binary(&path, opts)),
+            }
+        })
+        .collect();
+
+    print_table(&rows);
+    rows
+}
+
+
+/// Time a single run of a binary.
+///
+/// `process::run()` blocks until the binary exits rather than polling it, so
+/// even very fast challenges get accurate timings.
+fn run_binary(path: &str, opts: &Options) -> Result<::std::time::Duration, Outcome> {
+    let output = process::run(&mut Command::new(path), opts.timeout)
+        .map_err(|e| Outcome::Failed(e.to_string()))?;
+
+    match output.status {
+        Some(status) if status.success() => Ok(output.elapsed),
+        Some(status) if status.code() == Some(solution::TODO_EXIT_CODE) => Err(Outcome::Todo),
+        Some(status) => Err(Outcome::Failed(format!("Failed with {}", status))),
+        None => Err(Outcome::Failed(String::from("Timed out"))),
+    }
+}
+
+
+/// Benchmark solutions without leaving this process.
+pub fn in_process(solutions: &[&Solution], settings: Settings) -> Vec<Row> {
+    let rows: Vec<Row> = solutions.iter()
+        .map(|solution| {
+            let outcome = measure(settings, || {
+                if !solution.is_implemented() {
+                    return Err(Outcome::Todo);
+                }
+
+                let (answer, duration) = solve_in_process(*solution);
+                answer.map(|_| duration.to_std().unwrap()).map_err(Outcome::Failed)
+            });
+            Row {
+                number: solution.number(),
+                name: format!("challenge_{}", solution.number()),
+                outcome: outcome,
+            }
+        })
+        .collect();
+
+    print_table(&rows);
+    rows
+}
+
+
+/// Do the warm-up runs then time each of the measured runs, giving up as
+/// soon as one fails (or turns out not to be implemented).
+fn measure<F>(settings: Settings, mut run: F) -> Outcome
+    where F: FnMut() -> Result<::std::time::Duration, Outcome>
+{
+    for _ in 0..settings.warmup {
+        if let Err(outcome) = run() {
+            return outcome;
+        }
+    }
+
+    let mut samples = Vec::with_capacity(settings.runs);
+    for _ in 0..settings.runs {
+        match run() {
+            Ok(duration) => {
+                let duration = time::Duration::from_std(duration).unwrap();
+                samples.push(duration.num_nanoseconds().unwrap_or(i64::max_value()) as f64 / 1e6);
+            }
+            Err(outcome) => return outcome,
+        }
+    }
+
+    match Stats::from_samples(&samples) {
+        Some(stats) => Outcome::Measured(stats),
+        None => Outcome::Failed(String::from("No measured runs")),
+    }
+}
+
+
+fn print_table(rows: &[Row]) {
+    let name_width = rows.iter().map(|r| r.name.len()).max().unwrap_or(0);
+
+    println!("{:<name_width$}  {:>10}  {:>10}  {:>10}  {:>10}  {:>8}",
+             "",
+             "min",
+             "median",
+             "mean",
+             "std dev",
+             "outliers",
+             name_width = name_width);
+
+    for row in rows {
+        let name = &row.name;
+        match row.outcome {
+            Outcome::Measured(ref stats) => {
+                println!("{:<name_width$}  {:>8.3}ms  {:>8.3}ms  {:>8.3}ms  {:>8.3}ms  {:>8}",
+                         name,
+                         stats.min,
+                         stats.median,
+                         stats.mean,
+                         stats.std_dev,
+                         stats.outliers,
+                         name_width = name_width)
+            }
+            Outcome::Todo => {
+                println!("{:<name_width$}  {}",
+                         name,
+                         Yellow.paint("TODO"),
+                         name_width = name_width)
+            }
+            Outcome::Failed(ref msg) => {
+                println!("{:<name_width$}  {}",
+                         name,
+                         Red.paint(msg.as_str()),
+                         name_width = name_width)
+            }
+        }
+    }
+}
+
+
+/// Pin the current process (and any children it spawns from now on) to a
+/// single CPU.
+#[cfg(target_os = "linux")]
+pub fn pin_to_cpu(cpu: usize) -> io::Result<()> {
+    use std::cmp;
+    use std::mem;
+    use libc;
+    use pool;
+
+    let cpus = cmp::min(pool::available_cpus(), libc::CPU_SETSIZE as usize);
+    if cpu >= cpus {
+        let msg = format!("CPUs are numbered from 0 to {}", cpus - 1);
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, msg));
+    }
+
+    unsafe {
+        let mut set: libc::cpu_set_t = mem::zeroed();
+        libc::CPU_ZERO(&mut set);
+        libc::CPU_SET(cpu, &mut set);
+
+        if libc::sched_setaffinity(0, mem::size_of::<libc::cpu_set_t>(), &set) == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+}
+
+/// Pin the current process (and any children it spawns from now on) to a
+/// single CPU.
+#[cfg(not(target_os = "linux"))]
+pub fn pin_to_cpu(_cpu: usize) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Other,
+                       "Pinning to a CPU is only supported on Linux"))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn pinning_to_a_cpu_which_does_not_exist_fails() {
+        let err = pin_to_cpu(100_000).unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn summarise_some_samples() {
+        let got = Stats::from_samples(&[5.0, 1.0, 4.0, 2.0, 3.0]).unwrap();
+
+        assert_eq!(got.min, 1.0);
+        assert_eq!(got.median, 3.0);
+        assert_eq!(got.mean, 3.0);
+        assert!((got.std_dev - 2.5_f64.sqrt()).abs() < 1e-9);
+        assert_eq!(got.outliers, 0);
+    }
+
+    #[test]
+    fn detect_outliers() {
+        let got = Stats::from_samples(&[10.0, 10.5, 10.2, 9.8, 10.1, 10.3, 55.0]).unwrap();
+
+        assert_eq!(got.median, 10.2);
+        assert_eq!(got.outliers, 1);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn fast_binaries_get_accurate_timings() {
+        let opts = Options {
+            timeout: Some(::std::time::Duration::from_secs(10)),
+            ..Options::default()
+        };
+        let settings = Settings {
+            warmup: 1,
+            runs: 10,
+        };
+
+        let got = match measure(settings, || run_binary("true", &opts)) {
+            Outcome::Measured(stats) => stats,
+            other => panic!("Unexpected outcome {:?}", other),
+        };
+
+        assert!(got.min < 5.0, "took {}ms", got.min);
+        assert!(got.median < 5.0, "took {}ms", got.median);
+    }
+
+    #[test]
+    fn unimplemented_challenges_are_skipped() {
+        let settings = Settings {
+            warmup: 0,
+            runs: 3,
+        };
+
+        let got = measure(settings, || Err(Outcome::Todo));
+
+        assert_eq!(got, Outcome::Todo);
+        assert_eq!(got.status(), "todo");
+    }
+
+    #[test]
+    fn a_single_sample() {
+        let got = Stats::from_samples(&[42.0]).unwrap();
+
+        assert_eq!(got.median, 42.0);
+        assert_eq!(got.std_dev, 0.0);
+        assert!(Stats::from_samples(&[]).is_none());
+    }
+}
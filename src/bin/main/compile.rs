@@ -0,0 +1,142 @@
+//! Compiling the challenge binaries, working out which challenges failed to
+//! build (and why) so the rest can still be run.
+
+use std::collections::HashMap;
+use std::process::Command;
+
+use serde_json::{self, Value};
+use time;
+
+use super::{Challenge, Options};
+
+
+/// The outcome of compiling a set of challenges.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Compiled {
+    /// How long compilation took.
+    pub duration: time::Duration,
+    /// The compiler's diagnostics for each challenge which failed to build,
+    /// keyed by binary name.
+    pub failures: HashMap<String, String>,
+}
+
+
+/// Compile the challenges, pinning any errors to the challenge they belong
+/// to.
+///
+/// Cargo stops building as soon as one target fails, so if anything goes
+/// wrong each challenge which wasn't blamed is built again on its own to
+/// find out whether it's actually fine. An error is only returned if
+/// compilation failed for a reason which can't be pinned on a challenge
+/// (e.g. a broken library).
+pub fn compile(binaries: &[Challenge], opts: &Options) -> Result<Compiled, String> {
+    let start = time::now();
+    let names: Vec<&str> = binaries.iter().map(|c| c.name.as_str()).collect();
+
+    let mut failures = build(&names, opts)?;
+
+    if !failures.is_empty() {
+        for name in names {
+            if !failures.contains_key(name) {
+                failures.extend(build(&[name], opts)?);
+            }
+        }
+    }
+
+    Ok(Compiled {
+        duration: time::now() - start,
+        failures: failures,
+    })
+}
+
+
+/// Run `cargo build` for some binaries, returning the diagnostics for each
+/// one which failed.
+fn build(names: &[&str], opts: &Options) -> Result<HashMap<String, String>, String> {
+    let mut cmd = Command::new("cargo");
+    cmd.arg("build").arg("--message-format=json");
+    if opts.as_release {
+        cmd.arg("--release");
+    }
+    for name in names {
+        cmd.arg("--bin").arg(name);
+    }
+
+    let output = cmd.output().map_err(|e| format!("Unable to run cargo ({})", e))?;
+    if output.status.success() {
+        return Ok(HashMap::new());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let errors = parse_errors(&stdout);
+
+    if errors.is_empty() || errors.keys().any(|target| !names.contains(&target.as_str())) {
+        // Something other than a challenge is broken, so there's no point
+        // carrying on
+        let mut msg = String::from("Compilation failed! :(\n");
+        for diagnostics in errors.values() {
+            msg.push_str(diagnostics);
+        }
+        msg.push_str(&String::from_utf8_lossy(&output.stderr));
+        return Err(msg);
+    }
+
+    Ok(errors)
+}
+
+
+/// Pull the rendered compiler errors for each target out of cargo's JSON
+/// messages.
+fn parse_errors(messages: &str) -> HashMap<String, String> {
+    let mut errors: HashMap<String, String> = HashMap::new();
+
+    for line in messages.lines() {
+        let msg: Value = match serde_json::from_str(line) {
+            Ok(msg) => msg,
+            Err(_) => continue,
+        };
+
+        if msg["reason"] != "compiler-message" || msg["message"]["level"] != "error" {
+            continue;
+        }
+
+        let target = match msg["target"]["name"].as_str() {
+            Some(target) => target,
+            None => continue,
+        };
+        let rendered = msg["message"]["rendered"]
+            .as_str()
+            .or_else(|| msg["message"]["message"].as_str())
+            .unwrap_or("");
+
+        let diagnostics = errors.entry(target.to_string()).or_insert_with(String::new);
+        diagnostics.push_str(rendered);
+        if !rendered.ends_with('\n') {
+            diagnostics.push('\n');
+        }
+    }
+
+    errors
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pin_errors_to_their_target() {
+        let messages = r#"{"reason":"compiler-artifact","target":{"name":"euler"}}
+{"reason":"compiler-message","target":{"kind":["bin"],"name":"challenge_98"},"message":{"level":"warning","message":"unused variable","rendered":"warning: unused variable\n"}}
+{"reason":"compiler-message","target":{"kind":["bin"],"name":"challenge_98"},"message":{"level":"error","message":"mismatched types","rendered":"error[E0308]: mismatched types\n"}}
+{"reason":"compiler-message","target":{"kind":["bin"],"name":"challenge_99"},"message":{"level":"error","message":"aborting due to previous error"}}
+not json
+"#;
+
+        let got = parse_errors(messages);
+
+        assert_eq!(got.len(), 2);
+        assert_eq!(got["challenge_98"], "error[E0308]: mismatched types\n");
+        assert_eq!(got["challenge_99"], "aborting due to previous error\n");
+    }
+}
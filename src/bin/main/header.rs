@@ -0,0 +1,195 @@
+//! Parsing the structured header at the top of each challenge's docstring.
+//!
+//! A header looks like this, with the tags and difficulty (Project Euler's
+//! percentage rating) being optional:
+//!
+//! ```text
+//! //! Challenge 18 - Maximum path sum I
+//! //! Tags: dp, data
+//! //! Difficulty: 5%
+//! //!
+//! //! By starting at the top of the triangle below...
+//! ```
+
+use std::cmp;
+
+use regex::Regex;
+
+
+/// Everything a challenge's header says about it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Header {
+    pub number: usize,
+    pub title: String,
+    pub tags: Vec<String>,
+    /// How hard the problem is, as a percentage.
+    pub difficulty: Option<u32>,
+}
+
+
+/// Parse the header from a challenge's docstring, checking it against the
+/// problem number from the challenge's filename.
+///
+/// Something sensible is always returned, falling back to the filename when
+/// the header is unusable, along with how many bytes of the docstring the
+/// header took up and warnings about anything which looked wrong.
+pub fn parse(docstring: &str, number: usize) -> (Header, usize, Vec<String>) {
+    let first_line = Regex::new(r"^(\w+) (\d+) - (.+)$").unwrap();
+    let field = Regex::new(r"^(\w+):\s*(.*)$").unwrap();
+
+    let mut header = Header {
+        number: number,
+        title: format!("challenge_{}", number),
+        tags: Vec::new(),
+        difficulty: None,
+    };
+    let mut warnings = Vec::new();
+    let mut lines = docstring.split('\n');
+
+    let title_line = lines.next().unwrap_or("");
+    let mut consumed = title_line.len() + 1;
+    let title_line = title_line.trim();
+    match first_line.captures(title_line) {
+        Some(caps) => {
+            let kind = caps.at(1).unwrap();
+            let n = caps.at(2).unwrap();
+            let title = caps.at(3).unwrap();
+
+            if kind != "Challenge" {
+                warnings.push(format!("The header should start with \"Challenge {}\", not \
+                                       \"{} {}\"",
+                                      number,
+                                      kind,
+                                      n));
+            }
+            if n.parse::<usize>() != Ok(number) {
+                warnings.push(format!("The header says this is problem {}, but the filename \
+                                       says it's problem {}",
+                                      n,
+                                      number));
+            }
+            header.title = title.trim().to_string();
+        }
+        None => {
+            warnings.push(format!("Expected a header like \"Challenge {} - Title\", found {:?}",
+                                  number,
+                                  title_line));
+            if !title_line.is_empty() {
+                header.title = title_line.to_string();
+            }
+        }
+    }
+
+    // Any fields come straight after the title, up until the first blank line
+    for line in lines {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            consumed += line.len() + 1;
+            break;
+        }
+
+        // Otherwise the problem statement started without leaving a gap
+        let caps = match field.captures(trimmed) {
+            Some(caps) => caps,
+            None => {
+                warnings.push(format!("Expected a blank line after the header, found {:?}",
+                                      trimmed));
+                break;
+            }
+        };
+        consumed += line.len() + 1;
+
+        let value = caps.at(2).unwrap().trim();
+
+        match caps.at(1).unwrap().to_lowercase().as_str() {
+            "tags" => {
+                header.tags = value.split(',')
+                    .map(|tag| tag.trim().to_lowercase())
+                    .filter(|tag| !tag.is_empty())
+                    .collect();
+            }
+            "difficulty" => {
+                match value.trim_right_matches('%').parse() {
+                    Ok(percent) if percent <= 100 => header.difficulty = Some(percent),
+                    _ => {
+                        warnings.push(format!("The difficulty should be a percentage, not {:?}",
+                                              value))
+                    }
+                }
+            }
+            other => warnings.push(format!("Unknown header field {:?}", other)),
+        }
+    }
+
+    (header, cmp::min(consumed, docstring.len()), warnings)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_a_full_header() {
+        let docstring = "Challenge 18 - Maximum path sum I\nTags: DP, data\nDifficulty: 5%\n\n\
+                         By starting at the top of the triangle below...\nFoo: bar\n";
+
+        let (got, consumed, warnings) = parse(docstring, 18);
+
+        assert!(docstring[consumed..].starts_with("By starting at the top"));
+        assert_eq!(got,
+                   Header {
+                       number: 18,
+                       title: String::from("Maximum path sum I"),
+                       tags: vec![String::from("dp"), String::from("data")],
+                       difficulty: Some(5),
+                   });
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn the_fields_are_optional() {
+        let (got, _, warnings) = parse("Challenge 1 - Multiples of 3 and 5\n\nIf we list...", 1);
+
+        assert_eq!(got.title, "Multiples of 3 and 5");
+        assert!(got.tags.is_empty());
+        assert_eq!(got.difficulty, None);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn warn_about_mismatched_headers() {
+        let (got, _, warnings) = parse("Problem 6 - Smallest Multiple\n", 5);
+
+        assert_eq!(got.number, 5);
+        assert_eq!(got.title, "Smallest Multiple");
+        assert_eq!(warnings.len(), 2);
+    }
+
+    #[test]
+    fn warn_about_malformed_headers() {
+        let docstring = "Smallest Multiple\nDifficulty: hard\nAuthor: me\n";
+        let (got, consumed, warnings) = parse(docstring, 5);
+
+        assert_eq!(got.title, "Smallest Multiple");
+        assert_eq!(got.difficulty, None);
+        assert_eq!(warnings.len(), 3);
+        assert_eq!(consumed, docstring.len());
+
+        let (got, consumed, warnings) = parse("", 5);
+        assert_eq!(got.title, "challenge_5");
+        assert_eq!(consumed, 0);
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn the_statement_can_follow_the_header_directly() {
+        let docstring = "Challenge 1 - Multiples\nTags: maths\nIf we list all...\n\nFind";
+
+        let (got, consumed, warnings) = parse(docstring, 1);
+
+        assert_eq!(got.tags, vec![String::from("maths")]);
+        assert_eq!(&docstring[consumed..], "If we list all...\n\nFind");
+        assert_eq!(warnings.len(), 1);
+    }
+}
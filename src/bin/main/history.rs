@@ -0,0 +1,539 @@
+//! A record of every run's timings and answers, used to spot performance
+//! regressions and answers which changed.
+//!
+//! The history is a JSON lines file (one run per line) so appending to it is
+//! cheap and it's easy to poke at with other tools.
+
+use std::env;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::ptr;
+
+use ansi_term::Colour::*;
+use serde_json::{self, Value};
+use time;
+
+use super::{Output, PACKAGE_ROOT};
+
+
+/// The environment variable which can be used to keep the history somewhere
+/// other than `history.jsonl` at the root of the repository.
+pub const HISTORY_VARIABLE: &'static str = "EULER_HISTORY";
+
+
+/// A single invocation of the runner.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    /// When the run happened, in RFC 3339 format.
+    pub timestamp: String,
+    /// The git commit which was checked out, if we could find it.
+    pub commit: Option<String>,
+    /// Were there uncommitted changes?
+    pub dirty: bool,
+    /// An optional name for the run (e.g. "baseline").
+    pub tag: Option<String>,
+    /// Whether the challenges were compiled with optimisations.
+    pub release: bool,
+    pub in_process: bool,
+    /// Was this a `bench` run (where each timing is a median over several
+    /// runs)?
+    pub bench: bool,
+    pub results: Vec<Record>,
+}
+
+/// How a single challenge went during a run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub number: usize,
+    pub status: String,
+    pub answer: Option<String>,
+    pub running_time_ms: f64,
+}
+
+impl Record {
+    /// Create a record from a challenge's results, leaving the answer out if
+    /// it's meant to be hidden.
+    pub fn from_output(output: &Output, hide_answer: bool) -> Record {
+        Record {
+            number: output.number,
+            status: output.status.name().to_string(),
+            answer: if hide_answer { None } else { output.answer.clone() },
+            running_time_ms: output.running_time.num_microseconds().unwrap_or(0) as f64 /
+                             1000.0,
+        }
+    }
+}
+
+impl Run {
+    /// Start a new run, recording the current time and git commit.
+    pub fn new(tag: Option<&str>, release: bool, in_process: bool, bench: bool) -> Run {
+        Run {
+            timestamp: time::now_utc().rfc3339().to_string(),
+            commit: git(&["rev-parse", "HEAD"]),
+            dirty: git(&["status", "--porcelain", "--untracked-files=no"])
+                .map(|changes| !changes.is_empty())
+                .unwrap_or(false),
+            tag: tag.map(|t| t.to_string()),
+            release: release,
+            in_process: in_process,
+            bench: bench,
+            results: Vec::new(),
+        }
+    }
+
+    /// Does this run match a tag or (an abbreviation of) a commit hash?
+    pub fn matches(&self, reference: &str) -> bool {
+        self.tag.as_ref().map(|t| t == reference).unwrap_or(false) ||
+        self.commit.as_ref().map(|c| c.starts_with(reference)).unwrap_or(false)
+    }
+
+    fn to_json(&self) -> Value {
+        let results: Vec<Value> = self.results
+            .iter()
+            .map(|r| {
+                json!({
+                    "number": r.number,
+                    "status": r.status,
+                    "answer": r.answer,
+                    "running_time_ms": r.running_time_ms,
+                })
+            })
+            .collect();
+
+        json!({
+            "timestamp": self.timestamp,
+            "commit": self.commit,
+            "dirty": self.dirty,
+            "tag": self.tag,
+            "release": self.release,
+            "in_process": self.in_process,
+            "bench": self.bench,
+            "results": results,
+        })
+    }
+
+    fn from_json(value: &Value) -> Option<Run> {
+        let string = |v: &Value| v.as_str().map(|s| s.to_string());
+
+        let mut results = Vec::new();
+        for r in value["results"].as_array()? {
+            results.push(Record {
+                number: r["number"].as_u64()? as usize,
+                status: string(&r["status"])?,
+                answer: string(&r["answer"]),
+                running_time_ms: r["running_time_ms"].as_f64()?,
+            });
+        }
+
+        Some(Run {
+            timestamp: string(&value["timestamp"])?,
+            commit: string(&value["commit"]),
+            dirty: value["dirty"].as_bool().unwrap_or(false),
+            tag: string(&value["tag"]),
+            release: value["release"].as_bool().unwrap_or(false),
+            in_process: value["in_process"].as_bool().unwrap_or(false),
+            bench: value["bench"].as_bool().unwrap_or(false),
+            results: results,
+        })
+    }
+
+    /// A short description of the run.
+    pub fn describe(&self) -> String {
+        let commit = match self.commit {
+            Some(ref commit) => commit.chars().take(8).collect(),
+            None => String::from("unknown commit"),
+        };
+        let mut description = format!("{} ({}", self.timestamp, commit);
+        if self.dirty {
+            description.push_str(", dirty");
+        }
+        if let Some(ref tag) = self.tag {
+            description.push_str(", tag ");
+            description.push_str(tag);
+        }
+        description.push_str(if self.release { ", release" } else { ", debug" });
+        description.push(')');
+        description
+    }
+}
+
+
+/// Where the history is kept.
+pub fn path() -> PathBuf {
+    match env::var_os(HISTORY_VARIABLE) {
+        Some(path) => PathBuf::from(path),
+        None => Path::new(PACKAGE_ROOT).join("history.jsonl"),
+    }
+}
+
+/// Add a run to the end of the history.
+pub fn append<P: AsRef<Path>>(path: P, run: &Run) -> io::Result<()> {
+    let mut f = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(f, "{}", run.to_json())
+}
+
+/// Read every run in the history, oldest first.
+pub fn load<P: AsRef<Path>>(path: P) -> Result<Vec<Run>, String> {
+    let path = path.as_ref();
+    let f = File::open(path)
+        .map_err(|e| format!("Unable to open {} ({})", path.display(), e))?;
+
+    let mut runs = Vec::new();
+    for (i, line) in BufReader::new(f).lines().enumerate() {
+        let line = line.map_err(|e| format!("Unable to read {} ({})", path.display(), e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let run = serde_json::from_str(&line)
+            .ok()
+            .and_then(|value| Run::from_json(&value))
+            .ok_or_else(|| format!("{}:{} isn't a valid run", path.display(), i + 1))?;
+        runs.push(run);
+    }
+
+    Ok(runs)
+}
+
+
+/// The latest run before `run` of the same kind, seeing as `bench` timings
+/// (medians of several runs) can't be fairly compared with normal ones.
+pub fn previous<'a>(runs: &'a [Run], run: &Run) -> Option<&'a Run> {
+    let index = runs.iter().position(|r| ptr::eq(r, run))?;
+    runs[..index].iter().rev().find(|r| r.bench == run.bench)
+}
+
+
+/// Something which changed between the baseline and candidate runs.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// The challenge got slower by more than the threshold.
+    Regression {
+        number: usize,
+        baseline_ms: f64,
+        candidate_ms: f64,
+    },
+    /// The challenge came up with a different answer.
+    AnswerChanged {
+        number: usize,
+        before: String,
+        after: String,
+    },
+}
+
+
+/// The median running time and latest answer for each challenge across a
+/// set of runs.
+#[derive(Debug, Clone, PartialEq)]
+struct Summary {
+    number: usize,
+    median_ms: Option<f64>,
+    answer: Option<String>,
+}
+
+fn summarise(runs: &[&Run]) -> Vec<Summary> {
+    let mut numbers: Vec<usize> = runs.iter()
+        .flat_map(|run| run.results.iter().map(|r| r.number))
+        .collect();
+    numbers.sort();
+    numbers.dedup();
+
+    numbers.into_iter()
+        .map(|number| {
+            let records: Vec<&Record> = runs.iter()
+                .flat_map(|run| run.results.iter())
+                .filter(|r| r.number == number)
+                .collect();
+
+            let mut times: Vec<f64> = records.iter()
+                .filter(|r| r.status == "success")
+                .map(|r| r.running_time_ms)
+                .collect();
+            times.sort_by(|a, b| a.partial_cmp(b).expect("Timings are never NaN"));
+
+            Summary {
+                number: number,
+                median_ms: median(&times),
+                answer: records.iter().rev().filter_map(|r| r.answer.clone()).next(),
+            }
+        })
+        .collect()
+}
+
+fn median(sorted: &[f64]) -> Option<f64> {
+    let n = sorted.len();
+
+    if n == 0 {
+        None
+    } else if n % 2 == 1 {
+        Some(sorted[n / 2])
+    } else {
+        Some((sorted[n / 2 - 1] + sorted[n / 2]) / 2.0)
+    }
+}
+
+
+/// Find every challenge whose median time went up by more than `threshold`
+/// percent, or whose answer changed.
+pub fn compare(baseline: &[&Run], candidate: &[&Run], threshold: f64) -> Vec<Change> {
+    let before = summarise(baseline);
+    let after = summarise(candidate);
+    let mut changes = Vec::new();
+
+    for new in &after {
+        let old = match before.iter().find(|s| s.number == new.number) {
+            Some(old) => old,
+            None => continue,
+        };
+
+        if let (Some(baseline_ms), Some(candidate_ms)) = (old.median_ms, new.median_ms) {
+            if candidate_ms > baseline_ms * (1.0 + threshold / 100.0) {
+                changes.push(Change::Regression {
+                    number: new.number,
+                    baseline_ms: baseline_ms,
+                    candidate_ms: candidate_ms,
+                });
+            }
+        }
+
+        if let (&Some(ref before), &Some(ref after)) = (&old.answer, &new.answer) {
+            if before != after {
+                changes.push(Change::AnswerChanged {
+                    number: new.number,
+                    before: before.clone(),
+                    after: after.clone(),
+                });
+            }
+        }
+    }
+
+    changes
+}
+
+
+/// Compare two sets of runs from the history, printing a table of every
+/// challenge they have in common and flagging anything which got worse.
+///
+/// Returns the number of problems found.
+pub fn print_comparison(baseline: &[&Run],
+                        candidate: &[&Run],
+                        threshold: f64,
+                        hide_answers: bool)
+                        -> usize {
+    println!("{}", Green.bold().paint("Baseline:"));
+    for run in baseline {
+        println!("  {}", run.describe());
+    }
+    println!("{}", Green.bold().paint("Candidate:"));
+    for run in candidate {
+        println!("  {}", run.describe());
+    }
+    if baseline.iter().chain(candidate.iter()).any(|r| r.release != candidate[0].release) {
+        println!("{}",
+                 Yellow.paint("Warning: comparing debug and release builds"));
+    }
+    println!("");
+
+    let before = summarise(baseline);
+    let after = summarise(candidate);
+    let changes = compare(baseline, candidate, threshold);
+
+    println!("{:>9}  {:>12}  {:>12}  {:>8}", "challenge", "baseline", "candidate", "change");
+    for new in &after {
+        let old = match before.iter().find(|s| s.number == new.number) {
+            Some(old) => old,
+            None => continue,
+        };
+
+        let (old_ms, new_ms) = match (old.median_ms, new.median_ms) {
+            (Some(old_ms), Some(new_ms)) => (old_ms, new_ms),
+            _ => continue,
+        };
+        let change = if old_ms > 0.0 {
+            format!("{:+.1}%", (new_ms - old_ms) / old_ms * 100.0)
+        } else {
+            String::from("-")
+        };
+        let regressed = changes.iter().any(|c| match *c {
+            Change::Regression { number, .. } => number == new.number,
+            _ => false,
+        });
+        let change = if regressed {
+            Red.bold().paint(format!("{:>8}", change)).to_string()
+        } else {
+            format!("{:>8}", change)
+        };
+
+        println!("{:>9}  {:>10.3}ms  {:>10.3}ms  {}", new.number, old_ms, new_ms, change);
+    }
+    println!("");
+
+    for change in &changes {
+        match *change {
+            Change::Regression { number, baseline_ms, candidate_ms } => {
+                println!("{} challenge_{} went from {:.3}ms to {:.3}ms",
+                         Red.bold().paint("Regression:"),
+                         number,
+                         baseline_ms,
+                         candidate_ms)
+            }
+            Change::AnswerChanged { number, ref before, ref after } => {
+                if hide_answers {
+                    println!("{} challenge_{}",
+                             Red.bold().paint("Answer changed:"),
+                             number)
+                } else {
+                    println!("{} challenge_{} went from {} to {}",
+                             Red.bold().paint("Answer changed:"),
+                             number,
+                             before,
+                             after)
+                }
+            }
+        }
+    }
+
+    if changes.is_empty() {
+        println!("{} (threshold {}%)",
+                 Green.paint("No regressions found"),
+                 threshold);
+    }
+
+    changes.len()
+}
+
+
+/// Run a git command in the repository, returning its trimmed output.
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(PACKAGE_ROOT)
+        .output()
+        .ok()?;
+
+    if output.status.success() {
+        String::from_utf8(output.stdout).ok().map(|s| s.trim().to_string())
+    } else {
+        None
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::Status;
+
+    fn run(commit: &str, tag: Option<&str>, results: &[(usize, &str, f64)]) -> Run {
+        Run {
+            timestamp: String::from("2017-10-01T12:00:00Z"),
+            commit: Some(commit.to_string()),
+            dirty: false,
+            tag: tag.map(|t| t.to_string()),
+            release: true,
+            in_process: false,
+            bench: false,
+            results: results.iter()
+                .map(|&(number, answer, ms)| {
+                    Record {
+                        number: number,
+                        status: String::from("success"),
+                        answer: Some(answer.to_string()),
+                        running_time_ms: ms,
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn round_trip_through_json() {
+        let original = run("abcdef", Some("baseline"), &[(1, "233168", 1.5)]);
+        let got = Run::from_json(&original.to_json()).unwrap();
+
+        assert_eq!(got, original);
+    }
+
+    #[test]
+    fn hidden_answers_are_not_recorded() {
+        let output = Output {
+            name: String::from("challenge_1"),
+            number: 1,
+            title: String::from("Multiples of 3 and 5"),
+            description: String::new(),
+            running_time: time::Duration::milliseconds(5),
+            peak_memory: None,
+            status: Status::Success,
+            answer: Some(String::from("233168")),
+            stderr: String::new(),
+            verdict: None,
+        };
+
+        assert_eq!(Record::from_output(&output, false).answer, output.answer);
+        assert_eq!(Record::from_output(&output, true).answer, None);
+    }
+
+    #[test]
+    fn compare_with_the_previous_run_of_the_same_kind() {
+        let mut runs = vec![run("a", None, &[]), run("b", None, &[]), run("c", None, &[])];
+        runs[1].bench = true;
+
+        assert_eq!(previous(&runs, &runs[2]).and_then(|r| r.commit.clone()),
+                   Some(String::from("a")));
+        assert_eq!(previous(&runs, &runs[1]), None);
+        assert_eq!(previous(&runs, &runs[0]), None);
+    }
+
+    #[test]
+    fn match_runs_by_tag_or_commit() {
+        let r = run("abcdef123", Some("baseline"), &[]);
+
+        assert!(r.matches("baseline"));
+        assert!(r.matches("abcdef"));
+        assert!(!r.matches("123"));
+        assert!(!r.matches("other"));
+    }
+
+    #[test]
+    fn flag_slow_challenges() {
+        let baseline = run("a", None, &[(1, "1", 10.0), (2, "2", 10.0)]);
+        let candidate = run("b", None, &[(1, "1", 10.5), (2, "2", 20.0)]);
+
+        let got = compare(&[&baseline], &[&candidate], 10.0);
+
+        assert_eq!(got,
+                   vec![Change::Regression {
+                            number: 2,
+                            baseline_ms: 10.0,
+                            candidate_ms: 20.0,
+                        }]);
+    }
+
+    #[test]
+    fn flag_changed_answers() {
+        let baseline = run("a", None, &[(1, "233168", 1.0)]);
+        let candidate = run("b", None, &[(1, "233169", 1.0)]);
+
+        let got = compare(&[&baseline], &[&candidate], 10.0);
+
+        assert_eq!(got,
+                   vec![Change::AnswerChanged {
+                            number: 1,
+                            before: String::from("233168"),
+                            after: String::from("233169"),
+                        }]);
+    }
+
+    #[test]
+    fn use_the_median_of_several_runs() {
+        let first = run("a", None, &[(1, "1", 10.0)]);
+        let second = run("a", None, &[(1, "1", 100.0)]);
+        let third = run("a", None, &[(1, "1", 12.0)]);
+        let candidate = run("b", None, &[(1, "1", 13.0)]);
+
+        let got = compare(&[&first, &second, &third], &[&candidate], 10.0);
+        assert!(got.is_empty());
+    }
+}
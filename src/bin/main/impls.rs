@@ -0,0 +1,176 @@
+//! Run every implementation of a problem side by side, making sure they all
+//! come up with the same answer and comparing how long each one takes.
+
+use std::cmp::min;
+use std::panic;
+
+use ansi_term::Colour::*;
+use time::Duration;
+
+use euler::solution::{Answer, Registry, Solution};
+
+use answers::{Answers, Verdict};
+use super::solve_in_process;
+
+
+/// The outcome of running one implementation.
+struct Row<'a> {
+    solution: &'a Solution,
+    answer: Result<Answer, String>,
+    fastest: Duration,
+}
+
+
+/// Compare the implementations of each problem in `problems`, or of every
+/// problem with more than one implementation if `problems` is empty.
+///
+/// Returns whether every implementation succeeded and agreed on the answer.
+pub fn compare(registry: &Registry,
+               answers: &Answers,
+               problems: &[usize],
+               runs: usize,
+               hide_answers: bool)
+               -> bool {
+    let mut problems = problems.to_vec();
+    if problems.is_empty() {
+        problems = registry.problems()
+            .iter()
+            .map(|s| s.number())
+            .filter(|&n| registry.implementations(n).len() > 1)
+            .collect();
+    }
+
+    // Panics get reported in the table
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut problematic = vec![];
+    for &number in &problems {
+        let implementations = registry.implementations(number);
+        if implementations.is_empty() {
+            println!("{} problem {}", Red.bold().paint("No implementations of"), number);
+            println!("");
+            problematic.push(number);
+            continue;
+        }
+
+        let rows: Vec<Row> = implementations.into_iter()
+            .map(|solution| run(solution, runs))
+            .collect();
+
+        if !print_table(&rows, answers, hide_answers) {
+            problematic.push(number);
+        }
+    }
+
+    let _ = panic::take_hook();
+
+    if !problematic.is_empty() {
+        let numbers: Vec<String> = problematic.iter().map(|n| n.to_string()).collect();
+        println!("{} {}", Red.bold().paint("Problems with errors:"), numbers.join(" "));
+    }
+
+    problematic.is_empty()
+}
+
+
+fn run<'a>(solution: &'a Solution, runs: usize) -> Row<'a> {
+    if !solution.is_implemented() {
+        return Row {
+            solution: solution,
+            answer: Err(String::from("Not implemented yet")),
+            fastest: Duration::zero(),
+        };
+    }
+
+    let (answer, mut fastest) = solve_in_process(solution);
+
+    if answer.is_ok() {
+        for _ in 1..runs {
+            let (_, duration) = solve_in_process(solution);
+            fastest = min(fastest, duration);
+        }
+    }
+
+    Row {
+        solution: solution,
+        answer: answer,
+        fastest: fastest,
+    }
+}
+
+
+/// Print a table comparing each implementation, returning whether they all
+/// succeeded and agreed on the (correct) answer.
+fn print_table(rows: &[Row], answers: &Answers, hide_answers: bool) -> bool {
+    let first = rows[0].solution;
+    println!("{} {} - {}",
+             Green.bold().paint("Problem"),
+             first.number(),
+             first.title());
+
+    let name_width = rows.iter().map(|r| r.solution.name().len()).max().unwrap_or(0);
+    let displayed: Vec<String> = rows.iter()
+        .map(|r| match r.answer {
+            Ok(_) if hide_answers => String::from("[hidden]"),
+            Ok(ref answer) => answer.to_string(),
+            Err(_) => String::from("error"),
+        })
+        .collect();
+    let answer_width = displayed.iter().map(|a| a.len()).max().unwrap_or(0);
+    let fastest = rows.iter().map(|r| r.fastest).min().unwrap();
+
+    for (row, answer) in rows.iter().zip(displayed.iter()) {
+        let ms = row.fastest.num_microseconds().unwrap_or(0) as f64 / 1000.0;
+        let relative = if fastest > Duration::zero() {
+            ms / (fastest.num_microseconds().unwrap_or(0) as f64 / 1000.0)
+        } else {
+            1.0
+        };
+
+        println!("  {:<name_width$}  {:>answer_width$}  {:>10.3}ms  {:>7.2}x",
+                 row.solution.name(),
+                 answer,
+                 ms,
+                 relative,
+                 name_width = name_width,
+                 answer_width = answer_width);
+
+        if let Err(ref msg) = row.answer {
+            println!("    {}", Red.paint(msg.as_str()));
+        }
+    }
+
+    let succeeded = rows.iter().all(|r| r.answer.is_ok());
+    let agreed = rows.iter().all(|r| r.answer == rows[0].answer);
+    let mut correct = true;
+
+    if !succeeded {
+        println!("  {}", Red.bold().paint("Not every implementation succeeded!"));
+    } else if !agreed {
+        println!("  {}", Red.bold().paint("The implementations disagree!"));
+    } else {
+        let answer = rows[0].answer.as_ref().unwrap();
+        match answers.check(first.number(), answer.as_str()) {
+            Verdict::Correct => {
+                println!("  {}", Green.paint("All implementations agree on the correct answer"))
+            }
+            Verdict::Wrong { expected } => {
+                correct = false;
+                let msg = Red.bold().paint("All implementations agree on the wrong answer!");
+                match expected {
+                    Some(ref expected) if !hide_answers => {
+                        println!("  {} (expected {})", msg, expected)
+                    }
+                    _ => println!("  {}", msg),
+                }
+            }
+            Verdict::Unknown => {
+                println!("  {} (no expected answer recorded)",
+                         Green.paint("All implementations agree"))
+            }
+        }
+    }
+    println!("");
+
+    succeeded && agreed && correct
+}
@@ -0,0 +1,1088 @@
+//! A binary that will find all completed challenges and run them.
+//!
+//! It works by first reading the /src/bin/ folder and finding any files
+//! matching "challenge_*.rs". Those challenges are built with a single
+//! `cargo build` (see the `compile` module), then each compiled binary is run
+//! directly as a child process (see the `process` module) with a timeout,
+//! while also printing out a bit of the challenge's docstring for context.
+//!
+//! Alternatively, every challenge is also compiled into this binary (see the
+//! `registry` module) so they can be run in-process with `--in-process`.
+//! That skips the compilation step, gives more precise timings and doesn't
+//! need the source tree to be around.
+
+#![feature(inclusive_range_syntax)]
+
+extern crate euler;
+extern crate env_logger;
+extern crate num;
+extern crate regex;
+extern crate ansi_term;
+extern crate time;
+extern crate clap;
+extern crate toml;
+extern crate md5;
+extern crate libc;
+extern crate term_size;
+#[cfg(target_os = "linux")]
+extern crate inotify;
+#[macro_use]
+extern crate serde_json;
+
+const PACKAGE_ROOT: &'static str = env!("CARGO_MANIFEST_DIR");
+
+mod answers;
+mod bench;
+mod compile;
+mod header;
+mod history;
+mod impls;
+mod pool;
+mod process;
+mod progress;
+mod registry;
+mod reporter;
+mod scaffold;
+mod selection;
+mod show;
+mod watch;
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::io::{self, Read, Write};
+use std::process::Command;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Once, ONCE_INIT};
+use std::time::{Duration, Instant};
+
+use ansi_term::Colour::*;
+use clap::{Arg, ArgMatches, App, SubCommand};
+
+use euler::solution::{self, Answer, Registry, Solution};
+
+use answers::{Answers, Verdict};
+use header::Header;
+use history::{Record, Run};
+use pool::Update;
+use progress::Progress;
+use reporter::{Format, Reporter};
+use selection::Selection;
+
+const VERSION: &'static str = env!("CARGO_PKG_VERSION");
+const AUTHORS: &'static str = env!("CARGO_PKG_AUTHORS");
+const NAME: &'static str = env!("CARGO_PKG_NAME");
+const DESCRIPTION: &'static str = env!("CARGO_PKG_DESCRIPTION");
+
+/// Exit code for when a challenge failed, timed out or got the wrong answer.
+const EXIT_FAILURE: i32 = 1;
+/// Exit code for when the challenges couldn't be run at all (e.g. they didn't
+/// compile).
+const EXIT_ERROR: i32 = 2;
+
+fn main() {
+    let matches = App::new(NAME)
+        .version(VERSION)
+        .author(AUTHORS)
+        .about(DESCRIPTION)
+        .arg(Arg::with_name("release")
+            .short("O")
+            .long("release")
+            .global(true)
+            .help("Compile in release mode (include all optimisations)"))
+        .arg(Arg::with_name("quiet")
+            .short("q")
+            .long("quiet")
+            .help("Supress unnecessary output"))
+        .arg(Arg::with_name("in-process")
+            .short("i")
+            .long("in-process")
+            .global(true)
+            .help("Run the solutions compiled into this binary instead of building each \
+                   challenge"))
+        .arg(Arg::with_name("format")
+            .short("f")
+            .long("format")
+            .takes_value(true)
+            .possible_values(Format::names())
+            .default_value("pretty")
+            .help("How to report the results"))
+        .arg(Arg::with_name("timeout")
+            .short("t")
+            .long("timeout")
+            .takes_value(true)
+            .default_value("60")
+            .global(true)
+            .validator(|s| s.parse::<u64>().map(|_| ()).map_err(|e| e.to_string()))
+            .help("Kill any challenge which runs for longer than this many seconds (0 to \
+                   disable, ignored with --in-process)"))
+        .arg(Arg::with_name("jobs")
+            .short("j")
+            .long("jobs")
+            .takes_value(true)
+            .validator(|s| positive(&s))
+            .help("How many challenges to run at once (defaults to the number of CPUs, use 1 \
+                   for clean timings, ignored with --in-process)"))
+        .arg(Arg::with_name("fail-fast")
+            .long("fail-fast")
+            .help("Stop as soon as a challenge fails, times out or gets the wrong answer"))
+        .arg(Arg::with_name("max-memory")
+            .long("max-memory")
+            .takes_value(true)
+            .value_name("MB")
+            .global(true)
+            .validator(|s| positive(&s))
+            .help("Fail any challenge whose peak memory usage goes over this many \
+                   megabytes (Linux only, ignored with --in-process)"))
+        .arg(Arg::with_name("challenges")
+            .multiple(true)
+            .validator(|s| selection::parse_range(&s).map(|_| ()))
+            .help("Only run these challenges (e.g. \"3 7 10-20\")"))
+        .arg(Arg::with_name("only")
+            .long("only")
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1)
+            .validator(|s| selection::parse_range(&s).map(|_| ()))
+            .help("Only run this challenge or range of challenges"))
+        .arg(Arg::with_name("match")
+            .short("m")
+            .long("match")
+            .takes_value(true)
+            .help("Only run challenges whose title matches this (case insensitive) regex"))
+        .arg(Arg::with_name("tag")
+            .long("tag")
+            .takes_value(true)
+            .global(true)
+            .help("Give this run a name in the history, so it can be compared against \
+                   later"))
+        .arg(Arg::with_name("no-history")
+            .long("no-history")
+            .global(true)
+            .help("Don't record this run in the history"))
+        .arg(Arg::with_name("hide-answers")
+            .long("hide-answers")
+            .global(true)
+            .help("Don't print any answers (they are still checked)"))
+        .subcommand(SubCommand::with_name("impls")
+            .about("Run every implementation of each problem, checking they agree and \
+                    comparing how long they take")
+            .arg(Arg::with_name("runs")
+                .short("n")
+                .long("runs")
+                .takes_value(true)
+                .default_value("1")
+                .validator(|s| positive(&s))
+                .help("How many times to run each implementation (the fastest run is \
+                       reported)"))
+            .arg(Arg::with_name("problems")
+                .multiple(true)
+                .validator(|s| positive(&s))
+                .help("Only compare these problems (defaults to every problem with more \
+                       than one implementation)")))
+        .subcommand(SubCommand::with_name("bench")
+            .about("Benchmark challenges, running each one several times and reporting \
+                    statistics about how long they took")
+            .arg(Arg::with_name("runs")
+                .short("n")
+                .long("runs")
+                .takes_value(true)
+                .default_value("10")
+                .validator(|s| positive(&s))
+                .help("How many measured runs to do for each challenge"))
+            .arg(Arg::with_name("warmup")
+                .short("w")
+                .long("warmup")
+                .takes_value(true)
+                .default_value("1")
+                .validator(|s| s.parse::<usize>().map(|_| ()).map_err(|e| e.to_string()))
+                .help("How many runs to do (and ignore) before measuring anything"))
+            .arg(Arg::with_name("pin")
+                .long("pin")
+                .takes_value(true)
+                .value_name("CPU")
+                .validator(|s| s.parse::<usize>().map(|_| ()).map_err(|e| e.to_string()))
+                .help("Pin the benchmarks to this CPU (Linux only)"))
+            .arg(Arg::with_name("challenges")
+                .multiple(true)
+                .validator(|s| selection::parse_range(&s).map(|_| ()))
+                .help("Only benchmark these challenges (e.g. \"3 7 10-20\")"))
+            .arg(Arg::with_name("match")
+                .short("m")
+                .long("match")
+                .takes_value(true)
+                .help("Only benchmark challenges whose title matches this (case \
+                       insensitive) regex")))
+        .subcommand(SubCommand::with_name("compare")
+            .about("Compare runs from the history, flagging any challenges which got \
+                    slower or whose answer changed")
+            .arg(Arg::with_name("baseline")
+                .short("b")
+                .long("baseline")
+                .takes_value(true)
+                .help("The tag or commit to compare against (defaults to the previous \
+                       run of the same kind)"))
+            .arg(Arg::with_name("candidate")
+                .short("c")
+                .long("candidate")
+                .takes_value(true)
+                .help("The tag or commit to check (defaults to the latest run)"))
+            .arg(Arg::with_name("threshold")
+                .long("threshold")
+                .takes_value(true)
+                .default_value("10")
+                .validator(|s| s.parse::<f64>().map(|_| ()).map_err(|e| e.to_string()))
+                .help("How many percent slower a challenge can get before it's flagged")))
+        .subcommand(SubCommand::with_name("watch")
+            .about("Run challenges, then rebuild and re-run them whenever they change")
+            .arg(Arg::with_name("challenges")
+                .multiple(true)
+                .validator(|s| selection::parse_range(&s).map(|_| ()))
+                .help("Only watch these challenges (e.g. \"3 7 10-20\")"))
+            .arg(Arg::with_name("match")
+                .short("m")
+                .long("match")
+                .takes_value(true)
+                .help("Only watch challenges whose title matches this (case insensitive) \
+                       regex")))
+        .subcommand(SubCommand::with_name("show")
+            .about("Print a challenge's full problem statement and how it did last time")
+            .arg(Arg::with_name("number")
+                .required(true)
+                .validator(|s| positive(&s))
+                .help("The problem's number"))
+            .arg(Arg::with_name("hide-data")
+                .long("hide-data")
+                .help("Leave out any blocks of data embedded in the problem statement"))
+            .arg(Arg::with_name("width")
+                .short("w")
+                .long("width")
+                .takes_value(true)
+                .validator(|s| positive(&s))
+                .help("Wrap the problem statement to this many columns (defaults to the \
+                       terminal's width)")))
+        .subcommand(SubCommand::with_name("new")
+            .about("Create a new challenge from a template")
+            .arg(Arg::with_name("number")
+                .required(true)
+                .validator(|s| positive(&s))
+                .help("The problem's number"))
+            .arg(Arg::with_name("title")
+                .required(true)
+                .help("The problem's title")))
+        .get_matches();
+
+    if let Some(matches) = matches.subcommand_matches("impls") {
+        let runs = matches.value_of("runs").and_then(|n| n.parse().ok()).unwrap_or(1);
+        let problems: Vec<usize> = matches.values_of("problems")
+            .map(|values| values.filter_map(|v| v.parse().ok()).collect())
+            .unwrap_or_default();
+
+        let agreed = impls::compare(&registry::registry(),
+                                    &Answers::load(),
+                                    &problems,
+                                    runs,
+                                    matches.is_present("hide-answers"));
+        exit(if agreed { 0 } else { EXIT_FAILURE });
+    }
+
+    if let Some(matches) = matches.subcommand_matches("bench") {
+        exit(run_benchmarks(matches));
+    }
+
+    if let Some(matches) = matches.subcommand_matches("compare") {
+        exit(compare_history(matches));
+    }
+
+    if let Some(matches) = matches.subcommand_matches("watch") {
+        exit(watch_challenges(matches));
+    }
+
+    if let Some(matches) = matches.subcommand_matches("show") {
+        exit(show_challenge(matches));
+    }
+
+    if let Some(matches) = matches.subcommand_matches("new") {
+        exit(new_challenge(matches));
+    }
+
+    exit(run_challenges(&matches));
+}
+
+
+/// Run the selected challenges, returning the exit code.
+fn run_challenges(matches: &ArgMatches) -> i32 {
+    let options = options(matches);
+    let selection = match selection(matches) {
+        Ok(selection) => selection,
+        Err(msg) => return error(&msg),
+    };
+
+    let answers = Answers::load();
+    let mut reporter = reporter::reporter(options.format, &options);
+
+    let results = if matches.is_present("in-process") {
+        execute_in_process(&registry::registry(),
+                           &selection,
+                           &answers,
+                           options,
+                           &mut *reporter)
+    } else {
+        let binaries: Vec<Challenge> = get_binaries()
+            .into_iter()
+            .filter(|c| selection.matches(c.number, &c.title()))
+            .collect();
+
+        match execute_binaries(binaries, &answers, options, &mut *reporter) {
+            Ok(results) => results,
+            Err(msg) => return error(&msg),
+        }
+    };
+
+    let mut run = new_run(matches, false);
+    run.results = results.iter()
+        .map(|out| Record::from_output(out, options.hide_answers))
+        .collect();
+    record(matches, &run);
+
+    if results.iter().any(Output::is_failure) {
+        EXIT_FAILURE
+    } else {
+        0
+    }
+}
+
+
+/// Keep re-running the selected challenges as they change, returning the
+/// exit code if anything goes wrong.
+fn watch_challenges(matches: &ArgMatches) -> i32 {
+    let selection = match selection(matches) {
+        Ok(selection) => selection,
+        Err(msg) => return error(&msg),
+    };
+
+    match watch::watch(&selection, options(matches)) {
+        Ok(()) => 0,
+        Err(msg) => error(&msg),
+    }
+}
+
+
+/// Print a challenge's problem statement, returning the exit code.
+fn show_challenge(matches: &ArgMatches) -> i32 {
+    let number = matches.value_of("number")
+        .and_then(|n| n.parse::<usize>().ok())
+        .expect("The problem number should be a positive integer");
+    let settings = show::Settings {
+        width: matches.value_of("width")
+            .and_then(|n| n.parse().ok())
+            .unwrap_or_else(show::Settings::default_width),
+        hide_data: matches.is_present("hide-data"),
+        hide_answers: matches.is_present("hide-answers"),
+    };
+
+    match get_binaries().into_iter().find(|c| c.number == number) {
+        Some(challenge) => {
+            show::show(&challenge, settings);
+            0
+        }
+        None => error(&format!("There is no challenge {}", number)),
+    }
+}
+
+
+/// Scaffold a new challenge, returning the exit code.
+fn new_challenge(matches: &ArgMatches) -> i32 {
+    let number = matches.value_of("number")
+        .and_then(|n| n.parse().ok())
+        .expect("The problem number should be a positive integer");
+    let title = matches.value_of("title").unwrap();
+
+    match scaffold::new_challenge(Path::new(PACKAGE_ROOT), number, title) {
+        Ok(files) => {
+            for file in files {
+                println!("{} {}", Green.bold().paint("Wrote"), file);
+            }
+            0
+        }
+        Err(msg) => error(&msg),
+    }
+}
+
+
+/// Print an error message, returning the exit code to use.
+fn error(msg: &str) -> i32 {
+    eprintln!("{} {}", Red.bold().paint("Error:"), msg);
+    EXIT_ERROR
+}
+
+
+/// Exit with the given code, making sure everything has been written out
+/// first.
+fn exit(code: i32) -> ! {
+    let _ = io::stdout().flush();
+    std::process::exit(code)
+}
+
+
+/// Benchmark the selected challenges, returning the exit code.
+fn run_benchmarks(matches: &ArgMatches) -> i32 {
+    let options = options(matches);
+    let settings = bench::Settings {
+        warmup: matches.value_of("warmup").and_then(|n| n.parse().ok()).unwrap_or(1),
+        runs: matches.value_of("runs").and_then(|n| n.parse().ok()).unwrap_or(10),
+    };
+    let selection = match selection(matches) {
+        Ok(selection) => selection,
+        Err(msg) => return error(&msg),
+    };
+
+    if let Some(cpu) = matches.value_of("pin").and_then(|n| n.parse().ok()) {
+        if let Err(e) = bench::pin_to_cpu(cpu) {
+            return error(&format!("Unable to pin to CPU {} ({})", cpu, e));
+        }
+    }
+
+    let rows = if matches.is_present("in-process") {
+        let registry = registry::registry();
+        let solutions: Vec<&Solution> = registry.problems()
+            .into_iter()
+            .filter(|solution| selection.matches(solution.number(), solution.title()))
+            .collect();
+
+        if solutions.is_empty() {
+            println!("{}", Yellow.paint("No challenges were selected"));
+            return 0;
+        }
+
+        // Panics are reported in the table
+        panic::set_hook(Box::new(|_| {}));
+        let rows = bench::in_process(&solutions, settings);
+        let _ = panic::take_hook();
+        rows
+    } else {
+        let binaries: Vec<Challenge> = get_binaries()
+            .into_iter()
+            .filter(|c| selection.matches(c.number, &c.title()))
+            .collect();
+
+        if binaries.is_empty() {
+            println!("{}", Yellow.paint("No challenges were selected"));
+            return 0;
+        }
+
+        let compiled = match compile::compile(&binaries, &options) {
+            Ok(compiled) => compiled,
+            Err(msg) => return error(&msg),
+        };
+        for challenge in &binaries {
+            if let Some(diagnostics) = compiled.failures.get(&challenge.name) {
+                eprintln!("{} failed to compile\n{}", challenge.name, diagnostics);
+            }
+        }
+
+        let (broken, binaries): (Vec<Challenge>, Vec<Challenge>) = binaries.into_iter()
+            .partition(|c| compiled.failures.contains_key(&c.name));
+        let mut rows = bench::binaries(&binaries, settings, &options);
+        rows.extend(broken.into_iter().map(|c| {
+            bench::Row {
+                number: c.number,
+                name: c.name,
+                outcome: bench::Outcome::Failed(String::from("Failed to compile")),
+            }
+        }));
+        rows
+    };
+
+    // Only the median of each challenge goes into the history
+    let mut run = new_run(matches, true);
+    run.results = rows.iter()
+        .map(|row| {
+            Record {
+                number: row.number,
+                status: String::from(row.outcome.status()),
+                answer: None,
+                running_time_ms: match row.outcome {
+                    bench::Outcome::Measured(ref stats) => stats.median,
+                    _ => 0.0,
+                },
+            }
+        })
+        .collect();
+    record(matches, &run);
+
+    // Unfinished challenges are skipped rather than counted as failures
+    let failed = rows.iter().any(|row| match row.outcome {
+        bench::Outcome::Failed(_) => true,
+        _ => false,
+    });
+    if failed {
+        EXIT_FAILURE
+    } else {
+        0
+    }
+}
+
+
+/// Compare runs from the history, returning the exit code.
+fn compare_history(matches: &ArgMatches) -> i32 {
+    let threshold = matches.value_of("threshold").and_then(|t| t.parse().ok()).unwrap_or(10.0);
+
+    let path = history::path();
+    let runs = match history::load(&path) {
+        Ok(runs) => runs,
+        Err(msg) => return error(&msg),
+    };
+
+    let candidate: Vec<&Run> = match matches.value_of("candidate") {
+        Some(reference) => runs.iter().filter(|r| r.matches(reference)).collect(),
+        None => runs.last().into_iter().collect(),
+    };
+    let baseline: Vec<&Run> = match matches.value_of("baseline") {
+        Some(reference) => runs.iter().filter(|r| r.matches(reference)).collect(),
+        None => {
+            candidate.first()
+                .and_then(|&latest| history::previous(&runs, latest))
+                .into_iter()
+                .collect()
+        }
+    };
+
+    if baseline.is_empty() || candidate.is_empty() {
+        return error(&format!("Couldn't find any runs to compare in {}", path.display()));
+    }
+
+    let problems = history::print_comparison(&baseline,
+                                             &candidate,
+                                             threshold,
+                                             matches.is_present("hide-answers"));
+
+    if problems > 0 { EXIT_FAILURE } else { 0 }
+}
+
+
+/// Start a new entry for the history.
+fn new_run(matches: &ArgMatches, bench: bool) -> Run {
+    Run::new(matches.value_of("tag"),
+             matches.is_present("release"),
+             matches.is_present("in-process"),
+             bench)
+}
+
+
+/// Add a run to the history, unless the user asked us not to.
+fn record(matches: &ArgMatches, run: &Run) {
+    if matches.is_present("no-history") || run.results.is_empty() {
+        return;
+    }
+
+    let path = history::path();
+    if let Err(e) = history::append(&path, run) {
+        println!("{} Unable to add this run to {} ({})",
+                 Yellow.bold().paint("Warning:"),
+                 path.display(),
+                 e);
+    }
+}
+
+
+/// Get the runner's options from the command line.
+fn options(matches: &ArgMatches) -> Options {
+    let timeout = matches.value_of("timeout")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0);
+
+    Options {
+        as_release: matches.is_present("release"),
+        quiet: matches.is_present("quiet"),
+        hide_answers: matches.is_present("hide-answers"),
+        format: matches.value_of("format")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_default(),
+        fail_fast: matches.is_present("fail-fast"),
+        jobs: matches.value_of("jobs")
+            .and_then(|n| n.parse().ok())
+            .unwrap_or_else(pool::available_cpus),
+        timeout: if timeout == 0 {
+            None
+        } else {
+            Some(Duration::from_secs(timeout))
+        },
+        max_memory: matches.value_of("max-memory")
+            .and_then(|mb| mb.parse::<u64>().ok())
+            .map(|mb| mb * 1024 * 1024),
+    }
+}
+
+
+/// Work out which challenges were asked for on the command line.
+fn selection(matches: &ArgMatches) -> Result<Selection, String> {
+    let specs = matches.values_of("challenges")
+        .into_iter()
+        .flat_map(|values| values)
+        .chain(matches.values_of("only").into_iter().flat_map(|values| values));
+
+    Selection::new(specs, matches.value_of("match"))
+}
+
+
+fn positive(s: &str) -> Result<(), String> {
+    match s.parse::<usize>() {
+        Ok(0) => Err(String::from("Should be at least 1")),
+        Ok(_) => Ok(()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+
+/// Every line of a file starting with "//!", without the comment markers.
+fn read_docstring(path: &str) -> String {
+    let mut f = fs::File::open(path).unwrap();
+    let mut contents = String::new();
+    f.read_to_string(&mut contents).unwrap();
+
+    contents.lines()
+        .filter(|line| line.starts_with("//!"))
+        .fold(String::new(), |mut s, line| {
+            let line = &line[3..];
+            s.push_str(if line.starts_with(' ') { &line[1..] } else { line });
+            s.push('\n');
+            s
+        })
+}
+
+
+/// Makes sure problems with the challenges' headers are only reported once,
+/// seeing as things like watch mode look for the challenges over and over.
+static HEADER_WARNINGS: Once = ONCE_INIT;
+
+/// Read the /src/bin/ directory and grab any file matching "challenge_*.rs".
+fn get_binaries() -> Vec<Challenge> {
+    let pattern = regex::Regex::new(r"challenge_(\d+).rs$").unwrap();
+    let challenge_directory = Path::new(PACKAGE_ROOT).join("src/bin");
+
+    let mut challenges = vec![];
+    let mut header_warnings = vec![];
+    for dir_entry in fs::read_dir(challenge_directory).unwrap() {
+        let path = dir_entry.unwrap().path();
+        let path: &str = path.to_str().unwrap();
+
+        if let Some(caps) = pattern.captures(path) {
+            let number = caps.at(1).unwrap().parse().unwrap();
+            let (header, _, warnings) = header::parse(&read_docstring(path), number);
+
+            let c = Challenge {
+                path: path.to_string(),
+                name: format!("challenge_{}", number),
+                number: number,
+                header: header,
+            };
+            header_warnings.extend(warnings.into_iter().map(|w| (number, w)));
+            challenges.push(c);
+        };
+    }
+
+    // Sort according to the challenge's number
+    challenges.sort_by(|a, b| a.number.cmp(&b.number));
+    header_warnings.sort_by(|a, b| a.0.cmp(&b.0));
+
+    HEADER_WARNINGS.call_once(|| {
+        for &(number, ref warning) in &header_warnings {
+            eprintln!("{} challenge_{}.rs: {}", Yellow.bold().paint("Warning:"), number, warning);
+        }
+    });
+
+    challenges
+}
+
+
+fn execute_binaries(binaries: Vec<Challenge>,
+                    answers: &Answers,
+                    opts: Options,
+                    reporter: &mut Reporter)
+                    -> Result<Vec<Output>, String> {
+    reporter.start(binaries.len()).unwrap();
+
+    let mut failures = HashMap::new();
+    if !binaries.is_empty() {
+        reporter.compiling().unwrap();
+        let compiled = compile::compile(&binaries, &opts)?;
+        reporter.compiled(compiled.duration).unwrap();
+        failures = compiled.failures;
+    }
+
+    let mut results = Vec::new();
+    let mut progress = Progress::new(binaries.len());
+
+    let jobs: Vec<(Challenge, Option<String>)> = binaries.into_iter()
+        .map(|challenge| {
+            let diagnostics = failures.remove(&challenge.name);
+            (challenge, diagnostics)
+        })
+        .collect();
+    let answers = answers.clone();
+
+    pool::run_in_order(jobs,
+                       opts.jobs,
+                       move |(challenge, diagnostics)| match diagnostics {
+                           Some(diagnostics) => challenge.compile_error(diagnostics),
+                           None => challenge.execute(&answers, &opts),
+                       },
+                       move |out: &Output| opts.fail_fast && out.is_failure(),
+                       |update| match update {
+                           Update::Progress { running, finished } => {
+                               progress.update(running, finished);
+                           }
+                           Update::Done(out) => {
+                               progress.clear();
+                               reporter.challenge(&out).unwrap();
+                               io::stdout().flush().unwrap();
+                               progress.draw();
+                               results.push(out);
+                           }
+                       });
+    progress.clear();
+
+    results.sort_by(|left, right| left.number.cmp(&right.number));
+    reporter.finish(&results).unwrap();
+    Ok(results)
+}
+
+
+/// Run every solution in the registry, one after another, without leaving
+/// this process.
+fn execute_in_process(registry: &Registry,
+                      selection: &Selection,
+                      answers: &Answers,
+                      opts: Options,
+                      reporter: &mut Reporter)
+                      -> Vec<Output> {
+    let solutions: Vec<&Solution> = registry.problems()
+        .into_iter()
+        .filter(|solution| selection.matches(solution.number(), solution.title()))
+        .collect();
+
+    // Panics are reported as part of the challenge's output, so stop the
+    // default hook from printing them as well
+    panic::set_hook(Box::new(|_| {}));
+
+    reporter.start(solutions.len()).unwrap();
+
+    let mut results = Vec::new();
+    for solution in solutions {
+        let out = run_solution(solution, answers);
+        reporter.challenge(&out).unwrap();
+        let failed = out.is_failure();
+        results.push(out);
+
+        if failed && opts.fail_fast {
+            break;
+        }
+    }
+
+    let _ = panic::take_hook();
+    reporter.finish(&results).unwrap();
+    results
+}
+
+
+fn run_solution(solution: &Solution, answers: &Answers) -> Output {
+    let mut out = Output {
+        name: format!("challenge_{}", solution.number()),
+        number: solution.number(),
+        title: solution.title().to_string(),
+        description: format!("Problem {} - {}\n", solution.number(), solution.title()),
+        running_time: time::Duration::zero(),
+        peak_memory: None,
+        status: Status::Todo,
+        answer: None,
+        stderr: String::new(),
+        verdict: None,
+    };
+
+    if !solution.is_implemented() {
+        return out;
+    }
+
+    let (answer, duration) = solve_in_process(solution);
+    out.running_time = duration;
+
+    match answer {
+        Ok(answer) => {
+            out.status = Status::Success;
+            out.verdict = Some(answers.check(solution.number(), answer.as_str()));
+            out.answer = Some(answer.to_string());
+        }
+        Err(msg) => {
+            out.status = Status::Failed(101);
+            out.stderr = msg;
+        }
+    }
+
+    out
+}
+
+
+/// Solve a problem and time how long it took, catching any panics.
+///
+/// Callers should install a panic hook first if they don't want panic
+/// messages printed to stderr.
+fn solve_in_process(solution: &Solution) -> (Result<Answer, String>, time::Duration) {
+    let start = Instant::now();
+    let answer = panic::catch_unwind(AssertUnwindSafe(|| solution.solve()));
+    let duration = time::Duration::from_std(start.elapsed()).unwrap();
+
+    (answer.map_err(|payload| panic_message(&payload)), duration)
+}
+
+
+/// Get the message a panic was started with.
+fn panic_message(payload: &Box<Any + Send>) -> String {
+    if let Some(msg) = payload.downcast_ref::<&str>() {
+        msg.to_string()
+    } else if let Some(msg) = payload.downcast_ref::<String>() {
+        msg.clone()
+    } else {
+        String::from("Unknown panic")
+    }
+}
+
+
+#[derive(Debug)]
+struct Challenge {
+    path: String,
+    name: String,
+    number: usize,
+    header: Header,
+}
+
+impl Challenge {
+    /// Get the challenge's title from its header.
+    fn title(&self) -> String {
+        self.header.title.clone()
+    }
+
+    /// Open up the file and grab the first 16 lines of its docstring.
+    fn read_docstring(&self) -> String {
+        self.full_docstring()
+            .lines()
+            .take(16)
+            .fold(String::new(), |mut s, line| {
+                s.push_str(line);
+                s.push('\n');
+                s
+            })
+    }
+
+    fn full_docstring(&self) -> String {
+        read_docstring(&self.path)
+    }
+
+    /// Where the compiled challenge ends up.
+    fn binary_path(&self, opts: &Options) -> String {
+        format!("{}/target/{}/{}",
+                PACKAGE_ROOT,
+                if opts.as_release { "release" } else { "debug" },
+                self.name)
+    }
+
+    fn execute(&self, answers: &Answers, opts: &Options) -> Output {
+        // Run the compiled binary, killing it if it takes too long
+        let binary = self.binary_path(opts);
+        let output = match process::run(&mut Command::new(&binary), opts.timeout) {
+            Ok(output) => output,
+            Err(e) => {
+                // e.g. the binary went missing, which is reported like a crash
+                let msg = format!("Unable to run {} ({})", binary, e);
+                return self.not_run(Status::Failed(-1), msg);
+            }
+        };
+
+        let mut verdict = None;
+        let mut answer = None;
+
+        let over_limit = match (output.peak_memory, opts.max_memory) {
+            (Some(used), Some(limit)) => used > limit,
+            _ => false,
+        };
+
+        let status = match output.status {
+            Some(status) if status.success() => {
+                verdict = Some(answers.check(self.number, &output.stdout));
+                answer = Some(output.stdout.trim().to_string());
+                if over_limit {
+                    Status::MemoryExceeded
+                } else {
+                    Status::Success
+                }
+            }
+            Some(status) if status.code() == Some(solution::TODO_EXIT_CODE) => Status::Todo,
+            Some(status) => Status::Failed(status.code().unwrap_or(-1)),
+            None => Status::Timeout,
+        };
+
+        Output {
+            name: self.name.clone(),
+            number: self.number,
+            title: self.title(),
+            description: self.read_docstring(),
+            running_time: time::Duration::from_std(output.elapsed).unwrap(),
+            peak_memory: output.peak_memory,
+            status: status,
+            answer: answer,
+            stderr: output.stderr,
+            verdict: verdict,
+        }
+    }
+
+    /// The result for a challenge which couldn't be compiled, along with the
+    /// compiler's diagnostics.
+    fn compile_error(&self, diagnostics: String) -> Output {
+        self.not_run(Status::CompileError, diagnostics)
+    }
+
+    /// The result for a challenge which never got as far as running.
+    fn not_run(&self, status: Status, stderr: String) -> Output {
+        Output {
+            name: self.name.clone(),
+            number: self.number,
+            title: self.title(),
+            description: self.read_docstring(),
+            running_time: time::Duration::zero(),
+            peak_memory: None,
+            status: status,
+            answer: None,
+            stderr: stderr,
+            verdict: None,
+        }
+    }
+}
+
+
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+struct Options {
+    pub as_release: bool,
+    pub quiet: bool,
+    pub hide_answers: bool,
+    pub format: Format,
+    /// Stop running challenges after the first failure.
+    pub fail_fast: bool,
+    /// How many challenge binaries to run at once.
+    pub jobs: usize,
+    /// How long a challenge binary may run before it gets killed.
+    pub timeout: Option<Duration>,
+    /// How much memory (in bytes) a challenge binary may use before it's
+    /// considered a failure.
+    pub max_memory: Option<u64>,
+}
+
+
+/// The results of running a single challenge.
+#[derive(Debug, Clone)]
+struct Output {
+    name: String,
+    number: usize,
+    title: String,
+    /// A bit of the challenge's docstring, to give some context.
+    description: String,
+    running_time: time::Duration,
+    /// The challenge's peak memory usage in bytes, if it was measured.
+    peak_memory: Option<u64>,
+    status: Status,
+    /// The answer the challenge came up with, if it finished successfully.
+    answer: Option<String>,
+    /// Anything the challenge wrote to stderr.
+    stderr: String,
+    /// How the answer compared to the expected one.
+    verdict: Option<Verdict>,
+}
+
+
+impl Output {
+    /// Did the challenge fail to compile, crash, time out, use too much
+    /// memory or get the wrong answer?
+    fn is_failure(&self) -> bool {
+        match self.status {
+            Status::Failed(_) |
+            Status::Timeout |
+            Status::MemoryExceeded |
+            Status::CompileError => true,
+            Status::Success => {
+                match self.verdict {
+                    Some(Verdict::Wrong { .. }) => true,
+                    _ => false,
+                }
+            }
+            Status::Todo => false,
+        }
+    }
+}
+
+
+/// How a challenge finished.
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Status {
+    Success,
+    /// The challenge failed with this exit code.
+    Failed(i32),
+    /// The challenge was killed for running longer than `--timeout`.
+    Timeout,
+    /// The challenge hasn't been solved yet.
+    Todo,
+    /// The challenge used more memory than `--max-memory` allows.
+    MemoryExceeded,
+    /// The challenge didn't compile, so it was never run.
+    CompileError,
+}
+
+impl Status {
+    /// The name used for this status in machine-readable output.
+    fn name(&self) -> &'static str {
+        match *self {
+            Status::Success => "success",
+            Status::Failed(_) => "failed",
+            Status::Timeout => "timeout",
+            Status::Todo => "todo",
+            Status::MemoryExceeded => "memory-exceeded",
+            Status::CompileError => "compile-error",
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registered_titles_match_the_headers() {
+        let challenges = get_binaries();
+
+        for solution in registry::registry().solutions() {
+            let challenge = challenges.iter()
+                .find(|c| c.number == solution.number())
+                .expect("Every registered solution should have a challenge binary");
+
+            assert_eq!(solution.title(), challenge.header.title, "{}", challenge.name);
+        }
+    }
+
+    #[test]
+    fn missing_binaries_are_failures() {
+        let (header, _, _) = header::parse("Challenge 999 - Missing\n", 999);
+        // The source exists, but there's no binary with this name
+        let challenge = Challenge {
+            path: format!("{}/src/bin/challenge_1.rs", PACKAGE_ROOT),
+            name: String::from("challenge_999"),
+            number: 999,
+            header: header,
+        };
+
+        let got = challenge.execute(&Answers::default(), &Options::default());
+
+        assert_eq!(got.status, Status::Failed(-1));
+        assert!(got.stderr.contains("challenge_999"), "{}", got.stderr);
+        assert!(got.is_failure());
+    }
+}
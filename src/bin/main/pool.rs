@@ -0,0 +1,196 @@
+//! Running jobs on a fixed number of worker threads while handing the results
+//! back in the order the jobs were given.
+
+use std::cmp;
+use std::collections::BTreeMap;
+use std::panic;
+use std::sync::{mpsc, Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+
+/// Something interesting happening while the jobs are run.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Update<R> {
+    /// A job has started or finished.
+    Progress { running: usize, finished: usize },
+    /// The result of the next job, in order.
+    Done(R),
+}
+
+enum Event<R> {
+    Started,
+    Finished(usize, R),
+}
+
+
+/// Run every job on `workers` threads, passing each result to `on_update` as
+/// soon as it (and every job before it) has finished.
+///
+/// As soon as any job's result satisfies `stop_on`, no more jobs are
+/// started. Jobs which were already running are still finished and handed
+/// back, as long as every job before them ran too.
+pub fn run_in_order<T, R, W, S, F>(jobs: Vec<T>,
+                                   workers: usize,
+                                   work: W,
+                                   stop_on: S,
+                                   mut on_update: F)
+    where T: Send + 'static,
+          R: Send + 'static,
+          W: Fn(T) -> R + Send + Sync + 'static,
+          S: Fn(&R) -> bool + Send + Sync + 'static,
+          F: FnMut(Update<R>)
+{
+    let workers = cmp::min(cmp::max(workers, 1), jobs.len());
+    let queue = Arc::new(Mutex::new(jobs.into_iter().enumerate()));
+    let stop = Arc::new(AtomicBool::new(false));
+    let work = Arc::new(work);
+    let stop_on = Arc::new(stop_on);
+    let (tx, rx) = mpsc::channel();
+
+    let handles: Vec<_> = (0..workers)
+        .map(|_| {
+            let queue = queue.clone();
+            let stop = stop.clone();
+            let work = work.clone();
+            let stop_on = stop_on.clone();
+            let tx = tx.clone();
+
+            thread::spawn(move || {
+                while !stop.load(Ordering::SeqCst) {
+                    let next = queue.lock().unwrap().next();
+                    let (index, job) = match next {
+                        Some(next) => next,
+                        None => break,
+                    };
+
+                    if tx.send(Event::Started).is_err() {
+                        break;
+                    }
+                    let result = work(job);
+                    if stop_on(&result) {
+                        stop.store(true, Ordering::SeqCst);
+                    }
+                    if tx.send(Event::Finished(index, result)).is_err() {
+                        break;
+                    }
+                }
+            })
+        })
+        .collect();
+    drop(tx);
+
+    let mut pending = BTreeMap::new();
+    let mut next = 0;
+    let mut running = 0;
+    let mut finished = 0;
+
+    // The channel closes once every worker has run out of jobs
+    for event in rx {
+        match event {
+            Event::Started => running += 1,
+            Event::Finished(index, result) => {
+                running -= 1;
+                finished += 1;
+                pending.insert(index, result);
+            }
+        }
+
+        on_update(Update::Progress {
+            running: running,
+            finished: finished,
+        });
+        while let Some(result) = pending.remove(&next) {
+            next += 1;
+            on_update(Update::Done(result));
+        }
+    }
+
+    for handle in handles {
+        if let Err(e) = handle.join() {
+            panic::resume_unwind(e);
+        }
+    }
+}
+
+
+/// The number of CPUs available, to use as the default number of workers.
+#[cfg(unix)]
+pub fn available_cpus() -> usize {
+    use libc;
+
+    let cpus = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) };
+    cmp::max(cpus, 1) as usize
+}
+
+/// The number of CPUs available, to use as the default number of workers.
+#[cfg(not(unix))]
+pub fn available_cpus() -> usize {
+    1
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    /// Run jobs which sleep for a number of milliseconds, stopping after any
+    /// which sleep for `stop_on`.
+    fn results(jobs: Vec<u64>, workers: usize, stop_on: u64) -> Vec<u64> {
+        let mut got = Vec::new();
+
+        run_in_order(jobs,
+                     workers,
+                     |ms| {
+                         thread::sleep(Duration::from_millis(ms));
+                         ms
+                     },
+                     move |&ms| ms == stop_on,
+                     |update| if let Update::Done(ms) = update {
+                         got.push(ms);
+                     });
+
+        got
+    }
+
+    #[test]
+    fn results_come_back_in_order() {
+        let got = results(vec![50, 10, 30, 0, 20], 3, 1000);
+
+        assert_eq!(got, vec![50, 10, 30, 0, 20]);
+    }
+
+    #[test]
+    fn stop_early() {
+        let got = results(vec![0, 0, 1, 0, 0, 0], 1, 1);
+
+        assert_eq!(got, vec![0, 0, 1]);
+    }
+
+    #[test]
+    fn stop_before_earlier_jobs_finish() {
+        // The second job stops everything while the first is still running
+        let got = results(vec![100, 1, 0, 0, 0, 0], 2, 1);
+
+        assert_eq!(got, vec![100, 1]);
+    }
+
+    #[test]
+    fn progress_counts_running_jobs() {
+        let mut most_running = 0;
+        let mut last = None;
+
+        run_in_order(vec![20; 6],
+                     2,
+                     |ms| thread::sleep(Duration::from_millis(ms)),
+                     |_| false,
+                     |update| if let Update::Progress { running, finished } = update {
+                         most_running = cmp::max(most_running, running);
+                         last = Some((running, finished));
+                     });
+
+        assert_eq!(most_running, 2);
+        assert_eq!(last, Some((0, 6)));
+    }
+}
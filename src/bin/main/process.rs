@@ -0,0 +1,287 @@
+//! Running a challenge binary as a child process, making sure a solution
+//! which never finishes can't hold up everything else.
+//!
+//! On Linux and macOS the child is waited on with a blocking `waitid()` (on
+//! a helper thread when there's a timeout) so the timings aren't rounded up
+//! to a polling interval, then reaped with `wait4()`/`waitpid()`. On Linux
+//! `wait4()` also tells us how much memory it used.
+
+use std::io::{self, Read};
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+
+/// What happened when a child process was run.
+#[derive(Debug)]
+pub struct Finished {
+    /// How the process exited, or `None` if it was killed for taking too
+    /// long.
+    pub status: Option<ExitStatus>,
+    pub stdout: String,
+    pub stderr: String,
+    /// How long the process ran for.
+    pub elapsed: Duration,
+    /// The process's peak resident set size in bytes, if the platform can
+    /// tell us.
+    pub peak_memory: Option<u64>,
+}
+
+
+/// Run a command to completion, killing it if it runs for longer than
+/// `timeout`.
+///
+/// The child's stdout and stderr are read on background threads while we
+/// wait, otherwise a chatty child could fill up the pipe and block forever.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub fn run(cmd: &mut Command, timeout: Option<Duration>) -> io::Result<Finished> {
+    let start = Instant::now();
+    let mut child = cmd.stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+    let pid = child.id();
+
+    let (status, peak_memory, elapsed) = match timeout {
+        None => {
+            let (status, peak_memory) = wait(pid)?;
+            (Some(status), peak_memory, start.elapsed())
+        }
+        Some(timeout) => {
+            // Note when the child exited straight away, so the time it takes
+            // to hear back from the waiting thread doesn't count
+            let (tx, rx) = mpsc::channel();
+            thread::spawn(move || {
+                let waited = wait_for_exit(pid);
+                let _ = tx.send((waited, Instant::now()));
+            });
+
+            match rx.recv_timeout(timeout) {
+                Ok((waited, exited)) => {
+                    waited?;
+                    let (status, peak_memory) = wait(pid)?;
+                    (Some(status), peak_memory, exited - start)
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    // Nothing reaps the child until we do, so its pid can't
+                    // have been handed to another process yet
+                    let _ = child.kill();
+                    let (waited, exited) = rx.recv()
+                        .expect("The waiting thread always sends a result");
+                    waited?;
+                    let (_, peak_memory) = wait(pid)?;
+                    (None, peak_memory, exited - start)
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    unreachable!("The waiting thread always sends a result")
+                }
+            }
+        }
+    };
+
+    Ok(Finished {
+        status: status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+        elapsed: elapsed,
+        peak_memory: peak_memory,
+    })
+}
+
+/// Run a command to completion, killing it if it runs for longer than
+/// `timeout`.
+///
+/// Without a way to wait on a process by its ID, the child is polled every
+/// millisecond when there's a timeout.
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn run(cmd: &mut Command, timeout: Option<Duration>) -> io::Result<Finished> {
+    let start = Instant::now();
+    let mut child = cmd.stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let status = match timeout {
+        None => Some(child.wait()?),
+        Some(timeout) => {
+            loop {
+                if let Some(status) = child.try_wait()? {
+                    break Some(status);
+                }
+                if start.elapsed() >= timeout {
+                    let _ = child.kill();
+                    child.wait()?;
+                    break None;
+                }
+                thread::sleep(Duration::from_millis(1));
+            }
+        }
+    };
+    let elapsed = start.elapsed();
+
+    Ok(Finished {
+        status: status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+        elapsed: elapsed,
+        peak_memory: None,
+    })
+}
+
+
+/// Block until the child exits, but leave it for `wait()` to reap.
+///
+/// Until it's reaped the child stays around as a zombie, so it's still safe
+/// to kill it by its pid.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn wait_for_exit(pid: u32) -> io::Result<()> {
+    use std::mem;
+    use libc;
+
+    loop {
+        let mut info: libc::siginfo_t = unsafe { mem::zeroed() };
+        let flags = libc::WEXITED | libc::WNOWAIT;
+
+        if unsafe { libc::waitid(libc::P_PID, pid as libc::id_t, &mut info, flags) } == -1 {
+            let e = io::Error::last_os_error();
+            if e.kind() != io::ErrorKind::Interrupted {
+                return Err(e);
+            }
+        } else {
+            return Ok(());
+        }
+    }
+}
+
+/// Reap the child, returning its exit status and peak memory usage.
+#[cfg(target_os = "linux")]
+fn wait(pid: u32) -> io::Result<(ExitStatus, Option<u64>)> {
+    use std::mem;
+    use std::os::unix::process::ExitStatusExt;
+    use libc;
+
+    loop {
+        let mut status = 0;
+        let mut usage: libc::rusage = unsafe { mem::zeroed() };
+
+        if unsafe { libc::wait4(pid as libc::pid_t, &mut status, 0, &mut usage) } == -1 {
+            let e = io::Error::last_os_error();
+            if e.kind() != io::ErrorKind::Interrupted {
+                return Err(e);
+            }
+        } else {
+            // Linux reports the maximum resident set size in kilobytes
+            let peak_memory = usage.ru_maxrss as u64 * 1024;
+            return Ok((ExitStatus::from_raw(status), Some(peak_memory)));
+        }
+    }
+}
+
+/// Reap the child, returning its exit status.
+#[cfg(target_os = "macos")]
+fn wait(pid: u32) -> io::Result<(ExitStatus, Option<u64>)> {
+    use std::os::unix::process::ExitStatusExt;
+    use libc;
+
+    loop {
+        let mut status = 0;
+
+        if unsafe { libc::waitpid(pid as libc::pid_t, &mut status, 0) } == -1 {
+            let e = io::Error::last_os_error();
+            if e.kind() != io::ErrorKind::Interrupted {
+                return Err(e);
+            }
+        } else {
+            return Ok((ExitStatus::from_raw(status), None));
+        }
+    }
+}
+
+
+/// Read everything from a pipe on a background thread.
+fn drain<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buffer);
+        }
+        String::from_utf8_lossy(&buffer).into_owned()
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capture_output() {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg("echo hello; echo oops >&2");
+
+        let got = run(&mut cmd, Some(Duration::from_secs(10))).unwrap();
+
+        assert!(got.status.unwrap().success());
+        assert_eq!(got.stdout, "hello\n");
+        assert_eq!(got.stderr, "oops\n");
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn measure_peak_memory() {
+        let mut cmd = Command::new("true");
+
+        let got = run(&mut cmd, None).unwrap();
+
+        assert!(got.status.unwrap().success());
+        assert!(got.peak_memory.unwrap() > 0);
+    }
+
+    #[test]
+    fn timings_are_not_rounded_up() {
+        for timeout in &[None, Some(Duration::from_secs(10))] {
+            // The fastest of a few runs, so a busy machine doesn't fail this
+            let fastest = (0..5)
+                .map(|_| {
+                    let got = run(&mut Command::new("true"), *timeout).unwrap();
+                    assert!(got.status.unwrap().success());
+                    got.elapsed
+                })
+                .min()
+                .unwrap();
+
+            assert!(fastest < Duration::from_millis(5), "took {:?}", fastest);
+        }
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    fn waiting_for_a_child_leaves_it_to_be_reaped() {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg("exit 3");
+        let child = cmd.spawn().unwrap();
+
+        wait_for_exit(child.id()).unwrap();
+        let (status, _) = wait(child.id()).unwrap();
+
+        assert_eq!(status.code(), Some(3));
+    }
+
+    #[test]
+    fn kill_a_process_which_takes_too_long() {
+        let mut cmd = Command::new("sleep");
+        cmd.arg("10");
+
+        let got = run(&mut cmd, Some(Duration::from_millis(50))).unwrap();
+
+        assert!(got.status.is_none());
+        assert!(got.elapsed < Duration::from_secs(5));
+    }
+}
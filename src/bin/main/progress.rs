@@ -0,0 +1,68 @@
+//! A live progress line on stderr, showing how far through the challenges we
+//! are.
+
+use std::io::{self, Write};
+
+
+pub struct Progress {
+    enabled: bool,
+    total: usize,
+    running: usize,
+    finished: usize,
+    drawn: bool,
+}
+
+impl Progress {
+    /// Only bother drawing anything if someone's watching.
+    pub fn new(total: usize) -> Progress {
+        Progress {
+            enabled: total > 0 && stderr_is_terminal(),
+            total: total,
+            running: 0,
+            finished: 0,
+            drawn: false,
+        }
+    }
+
+    pub fn update(&mut self, running: usize, finished: usize) {
+        self.running = running;
+        self.finished = finished;
+        self.draw();
+    }
+
+    /// Draw the progress line again (e.g. after it was cleared).
+    pub fn draw(&mut self) {
+        if !self.enabled {
+            return;
+        }
+
+        eprint!("\r\x1b[K[{}/{}] {} running",
+                self.finished,
+                self.total,
+                self.running);
+        let _ = io::stderr().flush();
+        self.drawn = true;
+    }
+
+    /// Get rid of the progress line so something else can be printed.
+    pub fn clear(&mut self) {
+        if self.drawn {
+            eprint!("\r\x1b[K");
+            let _ = io::stderr().flush();
+            self.drawn = false;
+        }
+    }
+}
+
+
+#[cfg(unix)]
+fn stderr_is_terminal() -> bool {
+    use libc;
+
+    unsafe { libc::isatty(libc::STDERR_FILENO) == 1 }
+}
+
+#[cfg(not(unix))]
+fn stderr_is_terminal() -> bool {
+    false
+}
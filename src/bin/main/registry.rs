@@ -0,0 +1,65 @@
+//! Every challenge, compiled straight into the runner so they can be
+//! executed in-process.
+//!
+//! Each `challenge_*.rs` file is still a standalone binary, so its `main()`
+//! (and any crate-level attributes) go unused when it's pulled in here. New
+//! challenges need to be added to both lists below (`main new` does this for
+//! you), and a challenge with several competing solutions should register
+//! each of them (the first one registered is treated as the main solution).
+
+#![allow(dead_code, unused_attributes)]
+
+use euler::solution::Registry;
+
+#[path = "../challenge_1.rs"]
+mod challenge_1;
+#[path = "../challenge_2.rs"]
+mod challenge_2;
+#[path = "../challenge_3.rs"]
+mod challenge_3;
+#[path = "../challenge_4.rs"]
+mod challenge_4;
+#[path = "../challenge_5.rs"]
+mod challenge_5;
+#[path = "../challenge_6.rs"]
+mod challenge_6;
+#[path = "../challenge_7.rs"]
+mod challenge_7;
+#[path = "../challenge_8.rs"]
+mod challenge_8;
+#[path = "../challenge_9.rs"]
+mod challenge_9;
+#[path = "../challenge_10.rs"]
+mod challenge_10;
+#[path = "../challenge_12.rs"]
+mod challenge_12;
+#[path = "../challenge_13.rs"]
+mod challenge_13;
+#[path = "../challenge_18.rs"]
+mod challenge_18;
+#[path = "../challenge_67.rs"]
+mod challenge_67;
+
+
+/// Create a registry containing every challenge.
+pub fn registry() -> Registry {
+    let mut registry = Registry::new();
+
+    registry.register(challenge_1::MultiplesOf3And5);
+    registry.register(challenge_2::EvenFibonacciNumbers);
+    registry.register(challenge_3::LargestPrimeFactor);
+    registry.register(challenge_4::LargestPalindromeProduct);
+    registry.register(challenge_4::PalindromeFirst);
+    registry.register(challenge_5::SmallestMultiple);
+    registry.register(challenge_6::SumSquareDifference);
+    registry.register(challenge_7::TenThousandAndFirstPrime);
+    registry.register(challenge_8::LargestProductInASeries);
+    registry.register(challenge_9::SpecialPythagoreanTriple);
+    registry.register(challenge_10::SummationOfPrimes);
+    registry.register(challenge_12::HighlyDivisibleTriangleNumbers);
+    registry.register(challenge_13::LargeSum);
+    registry.register(challenge_18::MaximumPathSumI);
+    registry.register(challenge_67::MaximumPathSumII);
+
+    registry
+}
@@ -0,0 +1,766 @@
+//! Rendering the runner's results.
+//!
+//! Running a challenge only produces a structured `Output`, it's up to a
+//! `Reporter` to turn that into something a human (or a CI server) can read.
+//! Reporters are told about each result as soon as it's available so they
+//! can stream them out, and get the full list at the end for any summary.
+
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use ansi_term::Colour::*;
+use ansi_term::Style;
+use serde_json::{self, Value};
+use time::Duration;
+
+use answers::Verdict;
+use super::{Options, Output, Status};
+
+
+/// How the runner should report its results.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Format {
+    /// Coloured text meant for humans.
+    Pretty,
+    /// The same as `Pretty`, but without any colours.
+    Plain,
+    /// A JSON array with one object per challenge.
+    Json,
+    /// One JSON object per line, written as each challenge finishes.
+    JsonLines,
+    /// A Markdown table.
+    Markdown,
+    /// JUnit-style XML, which most CI servers understand.
+    Junit,
+    /// The Test Anything Protocol.
+    Tap,
+}
+
+impl Format {
+    /// The name of every format, as accepted by `from_str()`.
+    pub fn names() -> &'static [&'static str] {
+        &["pretty", "plain", "json", "json-lines", "markdown", "junit", "tap"]
+    }
+}
+
+impl Default for Format {
+    fn default() -> Format {
+        Format::Pretty
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "pretty" => Ok(Format::Pretty),
+            "plain" => Ok(Format::Plain),
+            "json" => Ok(Format::Json),
+            "json-lines" => Ok(Format::JsonLines),
+            "markdown" => Ok(Format::Markdown),
+            "junit" => Ok(Format::Junit),
+            "tap" => Ok(Format::Tap),
+            other => Err(format!("Unknown output format, {:?}", other)),
+        }
+    }
+}
+
+
+/// Something which renders the runner's results as they come in.
+pub trait Reporter {
+    /// Called before any challenges are run.
+    fn start(&mut self, _challenges: usize) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Called before the challenge binaries get compiled.
+    fn compiling(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Called once the challenge binaries have been compiled.
+    fn compiled(&mut self, _duration: Duration) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Called as soon as each challenge finishes.
+    fn challenge(&mut self, _result: &Output) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Called once every challenge has been run, with the results ordered by
+    /// challenge number.
+    fn finish(&mut self, results: &[Output]) -> io::Result<()>;
+}
+
+
+/// Create a reporter which writes to stdout.
+pub fn reporter(format: Format, opts: &Options) -> Box<Reporter> {
+    let out = io::stdout();
+
+    match format {
+        Format::Pretty => Box::new(Text::new(out, true, *opts)),
+        Format::Plain => Box::new(Text::new(out, false, *opts)),
+        Format::Json => Box::new(Json::new(out, false, *opts)),
+        Format::JsonLines => Box::new(Json::new(out, true, *opts)),
+        Format::Markdown => Box::new(Markdown::new(out, *opts)),
+        Format::Junit => Box::new(Junit::new(out, *opts)),
+        Format::Tap => Box::new(Tap::new(out, *opts)),
+    }
+}
+
+
+/// Human-readable text, optionally with colours.
+pub struct Text<W> {
+    out: W,
+    colour: bool,
+    opts: Options,
+}
+
+impl<W: Write> Text<W> {
+    pub fn new(out: W, colour: bool, opts: Options) -> Text<W> {
+        Text {
+            out: out,
+            colour: colour,
+            opts: opts,
+        }
+    }
+
+    fn paint(&self, style: Style, text: &str) -> String {
+        if self.colour {
+            style.paint(text).to_string()
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn verdict(&mut self, result: &Output) -> io::Result<()> {
+        let line = match result.verdict {
+            Some(Verdict::Correct) => self.paint(Green.bold(), "Correct"),
+            Some(Verdict::Wrong { expected: Some(ref expected) }) if !self.opts.hide_answers => {
+                format!("{} (expected {})",
+                        self.paint(Red.bold(), "Wrong answer!"),
+                        expected)
+            }
+            Some(Verdict::Wrong { .. }) => self.paint(Red.bold(), "Wrong answer!"),
+            Some(Verdict::Unknown) => self.paint(Yellow.normal(), "No expected answer recorded"),
+            None => return Ok(()),
+        };
+
+        writeln!(self.out, "{}\n", line)
+    }
+
+    /// Print a space-separated list of every result matching a predicate,
+    /// if there are any.
+    fn list<F>(&mut self, style: Style, label: &str, results: &[Output], predicate: F)
+               -> io::Result<()>
+        where F: Fn(&Output) -> bool
+    {
+        let titles: Vec<String> = results.iter()
+            .filter(|r| predicate(r))
+            .map(|r| format!("{} - {}", r.number, r.title))
+            .collect();
+
+        if titles.is_empty() {
+            Ok(())
+        } else {
+            let label = self.paint(style, label);
+            writeln!(self.out, "{} {}", label, titles.join(", "))
+        }
+    }
+}
+
+impl<W: Write> Reporter for Text<W> {
+    fn compiling(&mut self) -> io::Result<()> {
+        let msg = self.paint(Green.normal(), "Re-compiling... ");
+        write!(self.out, "{}", msg)?;
+        self.out.flush()
+    }
+
+    fn compiled(&mut self, duration: Duration) -> io::Result<()> {
+        writeln!(self.out, "compilation took {}ms", duration.num_milliseconds())?;
+        writeln!(self.out, "")
+    }
+
+    fn challenge(&mut self, result: &Output) -> io::Result<()> {
+        if self.opts.quiet {
+            return Ok(());
+        }
+
+        let header = self.paint(Green.bold(), "Running challenge:");
+        writeln!(self.out, "{} {}", header, result.name)?;
+        writeln!(self.out, "{}", result.description)?;
+
+        match result.status {
+            Status::Success | Status::MemoryExceeded => {
+                let answer = match visible(result.answer.clone(), &self.opts) {
+                    Some(answer) => answer,
+                    None => String::from("[hidden]"),
+                };
+                let header = self.paint(Blue.bold(), "Solution:");
+                writeln!(self.out, "{}\n{}", header, answer)?;
+                self.verdict(result)?;
+
+                if result.status == Status::MemoryExceeded {
+                    let msg = self.paint(Red.bold(), "Used too much memory!");
+                    writeln!(self.out, "{}\n", msg)?;
+                }
+            }
+            Status::Failed(_) => {
+                let header = self.paint(Red.bold(), "An error occurred during execution");
+                let stderr = self.paint(Red.normal(), &result.stderr);
+                writeln!(self.out, "{}\n{}\n", header, stderr)?;
+            }
+            Status::Timeout => {
+                let header = self.paint(Red.bold(), "TIMEOUT");
+                writeln!(self.out,
+                         "{} (killed after {}s)\n",
+                         header,
+                         result.running_time.num_seconds())?;
+            }
+            Status::Todo => {
+                let header = self.paint(Yellow.bold(), "TODO");
+                writeln!(self.out, "{}\n", header)?;
+            }
+            Status::CompileError => {
+                let header = self.paint(Red.bold(), "Compilation failed");
+                writeln!(self.out, "{}\n{}", header, result.stderr)?;
+            }
+        }
+
+        if result.status != Status::Todo && result.status != Status::CompileError {
+            let label = self.paint(Green.normal(), "Running time:");
+            write!(self.out, "{} {:.3}ms", label, milliseconds(result))?;
+            if let Some(bytes) = result.peak_memory {
+                write!(self.out, " (peak memory {})", megabytes(bytes))?;
+            }
+            writeln!(self.out, "")?;
+        }
+        writeln!(self.out, "")
+    }
+
+    fn finish(&mut self, results: &[Output]) -> io::Result<()> {
+        if results.is_empty() {
+            let msg = self.paint(Yellow.normal(), "No challenges were selected");
+            return writeln!(self.out, "{}", msg);
+        }
+
+        writeln!(self.out, "--------")?;
+        let header = self.paint(Green.bold(), "Challenge summary");
+        writeln!(self.out, "{}", header)?;
+
+        // Unfinished challenges don't count towards the answers, and only
+        // successful runs count towards the timings
+        let attempted = results.iter().filter(|r| r.status != Status::Todo).count();
+        let timed: Vec<&Output> = results.iter().filter(|r| r.status == Status::Success).collect();
+
+        let ms = timed.iter()
+            .map(|r| r.running_time)
+            .fold(Duration::seconds(0), |acc, t| acc + t)
+            .num_milliseconds() as f64;
+        writeln!(self.out, "Total running time: {}ms", ms)?;
+        writeln!(self.out, "Number of challenges: {}", results.len())?;
+        if !timed.is_empty() {
+            writeln!(self.out, "Average time: {:.2}ms", ms / timed.len() as f64)?;
+        }
+
+        let correct = results.iter().filter(|r| r.verdict == Some(Verdict::Correct)).count();
+        writeln!(self.out, "Correct answers: {}/{}", correct, attempted)?;
+
+        self.list(Red.bold(), "Errors:", results, |r| match r.status {
+                Status::Failed(_) => true,
+                _ => false,
+            })?;
+        self.list(Red.bold(),
+                  "Failed to compile:",
+                  results,
+                  |r| r.status == Status::CompileError)?;
+        self.list(Yellow.bold(),
+                  "TODO:",
+                  results,
+                  |r| r.status == Status::Todo)?;
+        self.list(Red.bold(),
+                  "Timed out:",
+                  results,
+                  |r| r.status == Status::Timeout)?;
+        self.list(Red.bold(),
+                  "Too much memory:",
+                  results,
+                  |r| r.status == Status::MemoryExceeded)?;
+        self.list(Red.bold(), "Wrong answers:", results, |r| match r.verdict {
+                Some(Verdict::Wrong { .. }) => true,
+                _ => false,
+            })?;
+        self.list(Yellow.bold(),
+                  "Unknown answers:",
+                  results,
+                  |r| r.verdict == Some(Verdict::Unknown))
+    }
+}
+
+
+/// JSON, either as one big array at the end or one line per challenge as
+/// they finish.
+pub struct Json<W> {
+    out: W,
+    streaming: bool,
+    opts: Options,
+}
+
+impl<W: Write> Json<W> {
+    pub fn new(out: W, streaming: bool, opts: Options) -> Json<W> {
+        Json {
+            out: out,
+            streaming: streaming,
+            opts: opts,
+        }
+    }
+}
+
+impl<W: Write> Reporter for Json<W> {
+    fn challenge(&mut self, result: &Output) -> io::Result<()> {
+        if self.streaming {
+            let record = record(result, &self.opts);
+            writeln!(self.out, "{}", record)?;
+        }
+
+        Ok(())
+    }
+
+    fn finish(&mut self, results: &[Output]) -> io::Result<()> {
+        if self.streaming {
+            return Ok(());
+        }
+
+        let records: Vec<Value> = results.iter().map(|r| record(r, &self.opts)).collect();
+        serde_json::to_writer_pretty(&mut self.out, &records)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+        writeln!(self.out, "")
+    }
+}
+
+/// The JSON object for a single challenge.
+fn record(result: &Output, opts: &Options) -> Value {
+    json!({
+        "number": result.number,
+        "name": result.name,
+        "title": result.title,
+        "status": result.status.name(),
+        "verdict": result.verdict.as_ref().map(verdict_name),
+        "answer": visible(result.answer.clone(), opts),
+        "expected": visible(expected_answer(result), opts),
+        "running_time_ms": milliseconds(result),
+        "peak_memory_bytes": result.peak_memory,
+        "stderr": result.stderr,
+    })
+}
+
+
+/// A Markdown table with a row per challenge, followed by a short summary.
+pub struct Markdown<W> {
+    out: W,
+    opts: Options,
+}
+
+impl<W: Write> Markdown<W> {
+    pub fn new(out: W, opts: Options) -> Markdown<W> {
+        Markdown {
+            out: out,
+            opts: opts,
+        }
+    }
+}
+
+impl<W: Write> Reporter for Markdown<W> {
+    fn start(&mut self, _challenges: usize) -> io::Result<()> {
+        writeln!(self.out, "| # | Title | Status | Answer | Time (ms) | Memory |")?;
+        writeln!(self.out, "|--:|-------|--------|--------|----------:|-------:|")
+    }
+
+    fn challenge(&mut self, result: &Output) -> io::Result<()> {
+        let status = match (result.status, &result.verdict) {
+            (Status::Success, &Some(ref verdict)) => verdict_name(verdict),
+            (status, _) => status.name(),
+        };
+        let answer = visible(result.answer.clone(), &self.opts).unwrap_or_default();
+
+        let memory = result.peak_memory.map(megabytes).unwrap_or_default();
+
+        writeln!(self.out,
+                 "| {} | {} | {} | {} | {:.3} | {} |",
+                 result.number,
+                 escape_markdown(&result.title),
+                 status,
+                 escape_markdown(&answer),
+                 milliseconds(result),
+                 memory)
+    }
+
+    fn finish(&mut self, results: &[Output]) -> io::Result<()> {
+        let correct = results.iter().filter(|r| r.verdict == Some(Verdict::Correct)).count();
+        let attempted = results.iter().filter(|r| r.status != Status::Todo).count();
+
+        writeln!(self.out, "")?;
+        writeln!(self.out, "**Correct answers:** {}/{}", correct, attempted)
+    }
+}
+
+
+/// A JUnit XML report, where each challenge is a test case.
+///
+/// The report needs totals up front so nothing gets written until the end.
+pub struct Junit<W> {
+    out: W,
+    opts: Options,
+}
+
+impl<W: Write> Junit<W> {
+    pub fn new(out: W, opts: Options) -> Junit<W> {
+        Junit {
+            out: out,
+            opts: opts,
+        }
+    }
+}
+
+impl<W: Write> Reporter for Junit<W> {
+    fn finish(&mut self, results: &[Output]) -> io::Result<()> {
+        let failures = results.iter().filter(|r| r.is_failure()).count();
+        let skipped = results.iter().filter(|r| r.status == Status::Todo).count();
+        let total: f64 = results.iter().map(milliseconds).sum();
+
+        writeln!(self.out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(self.out,
+                 r#"<testsuite name="euler" tests="{}" failures="{}" skipped="{}" time="{:.3}">"#,
+                 results.len(),
+                 failures,
+                 skipped,
+                 total / 1000.0)?;
+
+        for result in results {
+            writeln!(self.out,
+                     r#"  <testcase classname="euler" name="{}" time="{:.3}">"#,
+                     escape_xml(&result.name),
+                     milliseconds(result) / 1000.0)?;
+
+            match result.status {
+                Status::Todo => {
+                    writeln!(self.out, r#"    <skipped message="Not implemented yet"/>"#)?
+                }
+                Status::Timeout => writeln!(self.out, r#"    <failure message="Timed out"/>"#)?,
+                Status::CompileError => {
+                    writeln!(self.out, r#"    <failure message="Failed to compile"/>"#)?
+                }
+                Status::MemoryExceeded => {
+                    writeln!(self.out,
+                             r#"    <failure message="Used too much memory ({})"/>"#,
+                             result.peak_memory.map(megabytes).unwrap_or_default())?
+                }
+                Status::Failed(code) => {
+                    writeln!(self.out,
+                             r#"    <failure message="Exited with status {}"/>"#,
+                             code)?
+                }
+                Status::Success => {
+                    if let Some(Verdict::Wrong { .. }) = result.verdict {
+                        let message = match visible(expected_answer(result), &self.opts) {
+                            Some(expected) => format!("Wrong answer, expected {}", expected),
+                            None => String::from("Wrong answer"),
+                        };
+                        writeln!(self.out,
+                                 r#"    <failure message="{}"/>"#,
+                                 escape_xml(&message))?;
+                    }
+                }
+            }
+
+            if let Some(answer) = visible(result.answer.clone(), &self.opts) {
+                writeln!(self.out,
+                         "    <system-out>{}</system-out>",
+                         escape_xml(&answer))?;
+            }
+            if !result.stderr.is_empty() {
+                writeln!(self.out,
+                         "    <system-err>{}</system-err>",
+                         escape_xml(&result.stderr))?;
+            }
+            writeln!(self.out, "  </testcase>")?;
+        }
+
+        writeln!(self.out, "</testsuite>")
+    }
+}
+
+
+/// The Test Anything Protocol.
+pub struct Tap<W> {
+    out: W,
+    opts: Options,
+    planned: usize,
+    count: usize,
+}
+
+impl<W: Write> Tap<W> {
+    pub fn new(out: W, opts: Options) -> Tap<W> {
+        Tap {
+            out: out,
+            opts: opts,
+            planned: 0,
+            count: 0,
+        }
+    }
+}
+
+impl<W: Write> Reporter for Tap<W> {
+    fn start(&mut self, challenges: usize) -> io::Result<()> {
+        self.planned = challenges;
+        writeln!(self.out, "TAP version 13")?;
+        writeln!(self.out, "1..{}", challenges)
+    }
+
+    fn challenge(&mut self, result: &Output) -> io::Result<()> {
+        self.count += 1;
+
+        let ok = if result.is_failure() || result.status == Status::Todo {
+            "not ok"
+        } else {
+            "ok"
+        };
+        write!(self.out, "{} {} - {}", ok, self.count, result.name)?;
+        if result.status == Status::Todo {
+            write!(self.out, " # TODO not implemented yet")?;
+        }
+        writeln!(self.out, "")?;
+
+        writeln!(self.out, "  ---")?;
+        writeln!(self.out, "  number: {}", result.number)?;
+        writeln!(self.out, "  status: {}", result.status.name())?;
+        if let Some(ref verdict) = result.verdict {
+            writeln!(self.out, "  verdict: {}", verdict_name(verdict))?;
+        }
+        if let Some(answer) = visible(result.answer.clone(), &self.opts) {
+            writeln!(self.out, "  answer: {:?}", answer)?;
+        }
+        if let Some(expected) = visible(expected_answer(result), &self.opts) {
+            writeln!(self.out, "  expected: {:?}", expected)?;
+        }
+        writeln!(self.out, "  running_time_ms: {:.3}", milliseconds(result))?;
+        if let Some(bytes) = result.peak_memory {
+            writeln!(self.out, "  peak_memory_bytes: {}", bytes)?;
+        }
+        if !result.stderr.is_empty() {
+            writeln!(self.out, "  stderr: |")?;
+            for line in result.stderr.lines() {
+                writeln!(self.out, "    {}", line)?;
+            }
+        }
+        writeln!(self.out, "  ...")
+    }
+
+    fn finish(&mut self, _results: &[Output]) -> io::Result<()> {
+        if self.count < self.planned {
+            writeln!(self.out, "Bail out! Stopped after the first failure")?;
+        }
+
+        Ok(())
+    }
+}
+
+
+fn verdict_name(verdict: &Verdict) -> &'static str {
+    match *verdict {
+        Verdict::Correct => "correct",
+        Verdict::Wrong { .. } => "wrong",
+        Verdict::Unknown => "unknown",
+    }
+}
+
+/// The answer a challenge should have gotten, as far as we know.
+fn expected_answer(result: &Output) -> Option<String> {
+    match result.verdict {
+        Some(Verdict::Correct) => result.answer.clone(),
+        Some(Verdict::Wrong { ref expected }) => expected.clone(),
+        _ => None,
+    }
+}
+
+/// Hide an answer if the user asked us to.
+fn visible(answer: Option<String>, opts: &Options) -> Option<String> {
+    if opts.hide_answers { None } else { answer }
+}
+
+fn milliseconds(result: &Output) -> f64 {
+    result.running_time.num_microseconds().unwrap_or(0) as f64 / 1000.0
+}
+
+fn megabytes(bytes: u64) -> String {
+    format!("{:.1}MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+fn escape_xml(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+fn escape_markdown(s: &str) -> String {
+    s.replace('|', "\\|")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn outputs() -> Vec<Output> {
+        vec![Output {
+                 name: String::from("challenge_1"),
+                 number: 1,
+                 title: String::from("Multiples of 3 and 5"),
+                 description: String::new(),
+                 running_time: Duration::milliseconds(5),
+                 peak_memory: Some(3 * 1024 * 1024),
+                 status: Status::Success,
+                 answer: Some(String::from("233168")),
+                 stderr: String::new(),
+                 verdict: Some(Verdict::Correct),
+             },
+             Output {
+                 name: String::from("challenge_12"),
+                 number: 12,
+                 title: String::from("Highly Divisible Triangle Numbers"),
+                 description: String::new(),
+                 running_time: Duration::zero(),
+                 peak_memory: None,
+                 status: Status::Todo,
+                 answer: None,
+                 stderr: String::new(),
+                 verdict: None,
+             },
+             Output {
+                 name: String::from("challenge_13"),
+                 number: 13,
+                 title: String::from("Large Sum"),
+                 description: String::new(),
+                 running_time: Duration::milliseconds(1),
+                 peak_memory: Some(1024 * 1024),
+                 status: Status::Failed(101),
+                 answer: None,
+                 stderr: String::from("thread 'main' panicked at <oops>"),
+                 verdict: None,
+             }]
+    }
+
+    /// Feed every result through a reporter, the same way the runner would.
+    fn render<R: Reporter>(mut reporter: R) {
+        let results = outputs();
+
+        reporter.start(results.len()).unwrap();
+        for result in &results {
+            reporter.challenge(result).unwrap();
+        }
+        reporter.finish(&results).unwrap();
+    }
+
+    fn json(opts: Options) -> Value {
+        let mut buffer = Vec::new();
+        render(Json::new(&mut buffer, false, opts));
+        serde_json::from_slice(&buffer).unwrap()
+    }
+
+    #[test]
+    fn json_records() {
+        let got = json(Options::default());
+
+        assert_eq!(got[0]["number"], 1);
+        assert_eq!(got[0]["status"], "success");
+        assert_eq!(got[0]["answer"], "233168");
+        assert_eq!(got[0]["expected"], "233168");
+        assert_eq!(got[0]["running_time_ms"], 5.0);
+        assert_eq!(got[0]["peak_memory_bytes"], 3 * 1024 * 1024);
+        assert_eq!(got[1]["status"], "todo");
+        assert_eq!(got[2]["status"], "failed");
+        assert_eq!(got[2]["stderr"], "thread 'main' panicked at <oops>");
+    }
+
+    #[test]
+    fn hidden_answers_are_left_out() {
+        let got = json(Options { hide_answers: true, ..Options::default() });
+
+        assert_eq!(got[0]["answer"], Value::Null);
+        assert_eq!(got[0]["expected"], Value::Null);
+    }
+
+    #[test]
+    fn stream_json_lines() {
+        let mut buffer = Vec::new();
+        render(Json::new(&mut buffer, true, Options::default()));
+        let got = String::from_utf8(buffer).unwrap();
+
+        let numbers: Vec<Value> = got.lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap()["number"].clone())
+            .collect();
+        assert_eq!(numbers, vec![1, 12, 13]);
+    }
+
+    #[test]
+    fn plain_text_has_no_colours() {
+        let mut buffer = Vec::new();
+        render(Text::new(&mut buffer, false, Options::default()));
+        let got = String::from_utf8(buffer).unwrap();
+
+        assert!(!got.contains('\x1b'));
+        assert!(got.contains("Running challenge: challenge_1\n"));
+        assert!(got.contains("Average time: 5.00ms\n"));
+        assert!(got.contains("Correct answers: 1/2\n"));
+        assert!(got.contains("TODO: 12 - Highly Divisible Triangle Numbers\n"));
+    }
+
+    #[test]
+    fn markdown_table() {
+        let mut buffer = Vec::new();
+        render(Markdown::new(&mut buffer, Options::default()));
+        let got = String::from_utf8(buffer).unwrap();
+
+        assert!(got.contains("| 1 | Multiples of 3 and 5 | correct | 233168 | 5.000 | 3.0MB |\n"));
+        assert!(got.contains("| 12 | Highly Divisible Triangle Numbers | todo |  | 0.000 |  |\n"));
+    }
+
+    #[test]
+    fn junit_report() {
+        let mut buffer = Vec::new();
+        render(Junit::new(&mut buffer, Options::default()));
+        let got = String::from_utf8(buffer).unwrap();
+
+        assert!(got.contains(r#"tests="3" failures="1" skipped="1""#));
+        assert!(got.contains(r#"<testcase classname="euler" name="challenge_1" time="0.005">"#));
+        assert!(got.contains("<system-err>thread &apos;main&apos; panicked at &lt;oops&gt;"));
+    }
+
+    #[test]
+    fn tap_report() {
+        let mut buffer = Vec::new();
+        render(Tap::new(&mut buffer, Options::default()));
+        let got = String::from_utf8(buffer).unwrap();
+        let plan: Vec<&str> = got.lines().filter(|l| !l.starts_with(' ')).collect();
+
+        assert_eq!(plan,
+                   vec!["TAP version 13",
+                        "1..3",
+                        "ok 1 - challenge_1",
+                        "not ok 2 - challenge_12 # TODO not implemented yet",
+                        "not ok 3 - challenge_13"]);
+    }
+}
@@ -0,0 +1,432 @@
+//! Scaffolding for new challenges (`main new`).
+//!
+//! This creates the challenge binary from a template, registers it so it can
+//! be run in-process, and leaves a placeholder in `answers.toml` for the
+//! answer once it's known.
+
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+
+
+/// Create a new challenge in the crate at `root`, returning the files which
+/// were changed.
+pub fn new_challenge(root: &Path, number: usize, title: &str) -> Result<Vec<String>, String> {
+    let binary = format!("src/bin/challenge_{}.rs", number);
+    if root.join(&binary).exists() {
+        return Err(format!("{} already exists", binary));
+    }
+
+    let name = struct_name(title);
+
+    let registry = read(root, "src/bin/main/registry.rs")?;
+    let registry = add_to_registry(&registry, number, &name)?;
+    let answers = read(root, "answers.toml")?;
+    let answers = add_placeholder_answer(&answers, number, title);
+
+    let files = vec![(binary, template(number, title, &name)),
+                     (String::from("src/bin/main/registry.rs"), registry),
+                     (String::from("answers.toml"), answers)];
+    write_all(root, &files)?;
+
+    Ok(files.into_iter().map(|(filename, _)| filename).collect())
+}
+
+
+/// The source for a new challenge binary.
+fn template(number: usize, title: &str, name: &str) -> String {
+    format!(r#"//! Challenge {number} - {title}
+//!
+//! TODO: Copy the problem description from https://projecteuler.net/problem={number}
+
+extern crate euler;
+
+use euler::solution::{{self, Answer, Solution}};
+
+
+/// TODO: Explain how the problem is solved.
+pub struct {name};
+
+impl Solution for {name} {{
+    fn number(&self) -> usize {{
+        {number}
+    }}
+
+    fn title(&self) -> &str {{
+        "{escaped_title}"
+    }}
+
+    fn is_implemented(&self) -> bool {{
+        // TODO: Remove this once the problem is solved
+        false
+    }}
+
+    fn solve(&self) -> Answer {{
+        // This isn't called while the solution is reported as unfinished
+        Answer::from("TODO")
+    }}
+}}
+
+fn main() {{
+    solution::run(&{name});
+}}
+
+
+#[cfg(test)]
+mod tests {{
+    #[test]
+    #[ignore]
+    fn worked_example() {{
+        // TODO: Check the worked example from the problem description
+        // assert_eq!(super::{name}.solve(), super::Answer::from(...));
+    }}
+}}
+"#,
+            number = number,
+            title = title,
+            escaped_title = title.replace('\\', "\\\\").replace('"', "\\\""),
+            name = name)
+}
+
+
+/// Turn a challenge's title into the name of its `Solution` type (e.g.
+/// "Longest Collatz sequence" becomes `LongestCollatzSequence`).
+fn struct_name(title: &str) -> String {
+    let mut name: String = title.split(|c: char| !c.is_alphanumeric())
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars.next()
+                .into_iter()
+                .flat_map(|c| c.to_uppercase())
+                .chain(chars)
+                .collect::<Vec<_>>()
+        })
+        .filter(|&c| (c as u32) < 128)
+        .collect();
+
+    if !name.starts_with(|c: char| c.is_alphabetic()) {
+        name.insert_str(0, "Challenge");
+    }
+
+    name
+}
+
+
+/// Add a challenge to both lists in `registry.rs`, keeping them sorted.
+fn add_to_registry(src: &str, number: usize, name: &str) -> Result<String, String> {
+    let module = Regex::new(r#"^#\[path = "\.\./challenge_(\d+)\.rs"\]$"#).unwrap();
+    let register = Regex::new(r"^\s*registry\.register\(challenge_(\d+)::").unwrap();
+
+    let lines: Vec<&str> = src.lines().collect();
+    let numbered = |pattern: &Regex| -> Vec<(usize, usize)> {
+        lines.iter()
+            .enumerate()
+            .filter_map(|(i, line)| {
+                pattern.captures(line)
+                    .and_then(|caps| caps.at(1))
+                    .and_then(|n| n.parse().ok())
+                    .map(|n| (i, n))
+            })
+            .collect()
+    };
+
+    let modules = numbered(&module);
+    let registrations = numbered(&register);
+    let (last_module, last_registration) = match (modules.last(), registrations.last()) {
+        (Some(&(m, _)), Some(&(r, _))) => (m, r),
+        _ => return Err(String::from("Couldn't find the challenges in registry.rs")),
+    };
+
+    if modules.iter().any(|&(_, n)| n == number) {
+        return Err(format!("challenge_{} is already in the registry", number));
+    }
+
+    // Each module is an attribute followed by the `mod` line
+    let module_at = modules.iter()
+        .find(|&&(_, n)| n > number)
+        .map(|&(i, _)| i)
+        .unwrap_or(last_module + 2);
+    let register_at = registrations.iter()
+        .find(|&&(_, n)| n > number)
+        .map(|&(i, _)| i)
+        .unwrap_or(last_registration + 1);
+
+    let mut out = String::with_capacity(src.len() + 128);
+    for (i, line) in lines.iter().enumerate() {
+        if i == module_at {
+            out.push_str(&format!("#[path = \"../challenge_{0}.rs\"]\nmod challenge_{0};\n",
+                                  number));
+        }
+        if i == register_at {
+            out.push_str(&format!("    registry.register(challenge_{}::{});\n", number, name));
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+
+    Ok(out)
+}
+
+
+/// Leave a commented-out entry in `answers.toml`, in order, ready to be
+/// filled in once the challenge is solved.
+fn add_placeholder_answer(src: &str, number: usize, title: &str) -> String {
+    let entry = Regex::new(r"^#?\s*(\d+) = ").unwrap();
+    let placeholder = format!("# {} = {{ md5 = \"\" }}  # {}", number, title);
+
+    let mut out = String::with_capacity(src.len() + placeholder.len() + 1);
+    let mut inserted = false;
+
+    for line in src.lines() {
+        let later = entry.captures(line)
+            .and_then(|caps| caps.at(1))
+            .and_then(|n| n.parse::<usize>().ok())
+            .map_or(false, |n| n > number);
+
+        if later && !inserted {
+            out.push_str(&placeholder);
+            out.push('\n');
+            inserted = true;
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+
+    if !inserted {
+        out.push_str(&placeholder);
+        out.push('\n');
+    }
+
+    out
+}
+
+
+fn read(root: &Path, filename: &str) -> Result<String, String> {
+    let mut contents = String::new();
+    File::open(root.join(filename))
+        .and_then(|mut f| f.read_to_string(&mut contents))
+        .map_err(|e| format!("Unable to read {} ({})", filename, e))?;
+    Ok(contents)
+}
+
+/// Write several files so that either all of them are changed or none are,
+/// leaving the challenge fully registered or not at all.
+///
+/// Everything is written next to where it should go first. Each file is then
+/// moved into place, keeping the original until every file made it, so the
+/// originals can be put back if anything goes wrong.
+fn write_all(root: &Path, files: &[(String, String)]) -> Result<(), String> {
+    let beside = |filename: &str, suffix: &str| -> PathBuf {
+        root.join(format!("{}.{}", filename, suffix))
+    };
+    let remove_staged = || {
+        for &(ref filename, _) in files {
+            let _ = fs::remove_file(beside(filename, "new"));
+        }
+    };
+
+    for &(ref filename, ref contents) in files {
+        let written = File::create(beside(filename, "new"))
+            .and_then(|mut f| f.write_all(contents.as_bytes()));
+
+        if let Err(e) = written {
+            remove_staged();
+            return Err(format!("Unable to write {} ({})", filename, e));
+        }
+    }
+
+    let mut backed_up = Vec::new();
+    let mut installed = Vec::new();
+    let mut failure = None;
+
+    for &(ref filename, _) in files {
+        let target = root.join(filename);
+
+        if target.exists() {
+            if let Err(e) = fs::rename(&target, beside(filename, "orig")) {
+                failure = Some((filename, e));
+                break;
+            }
+            backed_up.push(filename);
+        }
+        if let Err(e) = fs::rename(beside(filename, "new"), &target) {
+            failure = Some((filename, e));
+            break;
+        }
+        installed.push(filename);
+    }
+
+    match failure {
+        None => {
+            for filename in backed_up {
+                let _ = fs::remove_file(beside(filename, "orig"));
+            }
+            Ok(())
+        }
+        Some((filename, e)) => {
+            for installed in installed {
+                let _ = fs::remove_file(root.join(installed));
+            }
+            for original in backed_up {
+                let _ = fs::rename(beside(original, "orig"), root.join(original));
+            }
+            remove_staged();
+            Err(format!("Unable to write {} ({})", filename, e))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::process;
+    use std::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+
+    use super::*;
+
+    const REGISTRY: &'static str = r#"use euler::solution::Registry;
+
+#[path = "../challenge_1.rs"]
+mod challenge_1;
+#[path = "../challenge_67.rs"]
+mod challenge_67;
+
+
+pub fn registry() -> Registry {
+    let mut registry = Registry::new();
+
+    registry.register(challenge_1::MultiplesOf3And5);
+    registry.register(challenge_67::MaximumPathSumII);
+
+    registry
+}
+"#;
+
+    const ANSWERS: &'static str = "[answers]\n1 = \"a\"\n";
+
+    /// An empty crate to scaffold challenges into, which is deleted
+    /// afterwards.
+    struct Crate(PathBuf);
+
+    impl Crate {
+        fn new() -> Crate {
+            static COUNT: AtomicUsize = ATOMIC_USIZE_INIT;
+
+            let name = format!("euler-scaffold-{}-{}",
+                               process::id(),
+                               COUNT.fetch_add(1, Ordering::SeqCst));
+            let root = env::temp_dir().join(name);
+            fs::create_dir_all(root.join("src/bin/main")).unwrap();
+
+            File::create(root.join("src/bin/main/registry.rs"))
+                .and_then(|mut f| f.write_all(REGISTRY.as_bytes()))
+                .unwrap();
+            File::create(root.join("answers.toml"))
+                .and_then(|mut f| f.write_all(ANSWERS.as_bytes()))
+                .unwrap();
+
+            Crate(root)
+        }
+
+        /// Check nothing has been changed.
+        fn assert_untouched(&self) {
+            let mut files: Vec<PathBuf> = fs::read_dir(self.0.join("src/bin"))
+                .unwrap()
+                .map(|entry| entry.unwrap().path())
+                .collect();
+            files.sort();
+
+            assert_eq!(files, vec![self.0.join("src/bin/main")]);
+            assert_eq!(read(&self.0, "src/bin/main/registry.rs").unwrap(), REGISTRY);
+            assert_eq!(read(&self.0, "answers.toml").unwrap(), ANSWERS);
+        }
+    }
+
+    impl Drop for Crate {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn refuse_to_overwrite_a_challenge() {
+        let scratch = Crate::new();
+        new_challenge(&scratch.0, 14, "Longest Collatz sequence").unwrap();
+        let before = read(&scratch.0, "src/bin/challenge_14.rs").unwrap();
+
+        let err = new_challenge(&scratch.0, 14, "Something else").unwrap_err();
+
+        assert!(err.contains("already exists"), "{}", err);
+        assert_eq!(read(&scratch.0, "src/bin/challenge_14.rs").unwrap(), before);
+        let registry = read(&scratch.0, "src/bin/main/registry.rs").unwrap();
+        assert_eq!(registry.matches("challenge_14::").count(), 1);
+    }
+
+    #[test]
+    fn failed_writes_change_nothing() {
+        let scratch = Crate::new();
+        // Something in the way of the new answers.toml
+        fs::create_dir(scratch.0.join("answers.toml.new")).unwrap();
+
+        assert!(new_challenge(&scratch.0, 14, "Longest Collatz sequence").is_err());
+
+        scratch.assert_untouched();
+    }
+
+    #[test]
+    fn failed_renames_are_rolled_back() {
+        let scratch = Crate::new();
+        // The original answers.toml can't be moved out of the way, but only
+        // after the binary and registry were already moved into place
+        fs::create_dir(scratch.0.join("answers.toml.orig")).unwrap();
+
+        assert!(new_challenge(&scratch.0, 14, "Longest Collatz sequence").is_err());
+
+        scratch.assert_untouched();
+        assert!(!scratch.0.join("src/bin/main/registry.rs.orig").exists());
+        assert!(!scratch.0.join("answers.toml.new").exists());
+    }
+
+    #[test]
+    fn names_from_titles() {
+        assert_eq!(struct_name("Longest Collatz sequence"), "LongestCollatzSequence");
+        assert_eq!(struct_name("Maximum path sum I"), "MaximumPathSumI");
+        assert_eq!(struct_name("10001st prime"), "Challenge10001stPrime");
+        assert_eq!(struct_name("Multiples of 3 and 5"), "MultiplesOf3And5");
+    }
+
+    #[test]
+    fn register_in_order() {
+        let got = add_to_registry(REGISTRY, 14, "LongestCollatzSequence").unwrap();
+
+        assert!(got.contains("mod challenge_1;\n#[path = \"../challenge_14.rs\"]\nmod \
+                              challenge_14;\n#[path = \"../challenge_67.rs\"]"));
+        assert!(got.contains("MultiplesOf3And5);\n    \
+                              registry.register(challenge_14::LongestCollatzSequence);\n    \
+                              registry.register(challenge_67"));
+    }
+
+    #[test]
+    fn register_at_the_end() {
+        let got = add_to_registry(REGISTRY, 100, "Arranged").unwrap();
+
+        assert!(got.contains("mod challenge_67;\n#[path = \"../challenge_100.rs\"]\nmod \
+                              challenge_100;\n"));
+        assert!(got.contains("MaximumPathSumII);\n    \
+                              registry.register(challenge_100::Arranged);\n"));
+        assert!(add_to_registry(&got, 100, "Arranged").is_err());
+    }
+
+    #[test]
+    fn placeholder_answers_go_in_order() {
+        let answers = "[answers]\n1 = \"a\"\n67 = \"b\"\n";
+
+        assert_eq!(add_placeholder_answer(answers, 14, "Collatz"),
+                   "[answers]\n1 = \"a\"\n# 14 = { md5 = \"\" }  # Collatz\n67 = \"b\"\n");
+        assert_eq!(add_placeholder_answer(answers, 100, "Arranged"),
+                   "[answers]\n1 = \"a\"\n67 = \"b\"\n# 100 = { md5 = \"\" }  # Arranged\n");
+    }
+}
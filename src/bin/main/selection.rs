@@ -0,0 +1,116 @@
+//! Choosing which challenges to run, by number, range or title.
+
+use regex::Regex;
+
+
+/// Which challenges the user asked for.
+#[derive(Debug, Clone, Default)]
+pub struct Selection {
+    /// Inclusive ranges of challenge numbers. Empty means "everything".
+    ranges: Vec<(usize, usize)>,
+    /// A case-insensitive pattern to match against the challenge's title.
+    pattern: Option<Regex>,
+}
+
+impl Selection {
+    /// Create a selection from a list of numbers or ranges (e.g. `["3",
+    /// "10-20"]`) and an optional regex for the title.
+    pub fn new<'a, I>(specs: I, pattern: Option<&str>) -> Result<Selection, String>
+        where I: IntoIterator<Item = &'a str>
+    {
+        let ranges = specs.into_iter()
+            .map(parse_range)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let pattern = match pattern {
+            Some(p) => {
+                Some(Regex::new(&format!("(?i){}", p))
+                    .map_err(|e| format!("Invalid pattern, {:?} ({})", p, e))?)
+            }
+            None => None,
+        };
+
+        Ok(Selection {
+            ranges: ranges,
+            pattern: pattern,
+        })
+    }
+
+    /// Is this challenge one of the selected ones?
+    pub fn matches(&self, number: usize, title: &str) -> bool {
+        let number_matches = self.ranges.is_empty() ||
+                             self.ranges.iter().any(|&(low, high)| low <= number && number <= high);
+        let title_matches = match self.pattern {
+            Some(ref pattern) => pattern.is_match(title),
+            None => true,
+        };
+
+        number_matches && title_matches
+    }
+}
+
+
+/// Parse either a single challenge number or an inclusive range of them
+/// (e.g. "10-20").
+pub fn parse_range(spec: &str) -> Result<(usize, usize), String> {
+    let parse = |s: &str| {
+        s.trim()
+            .parse::<usize>()
+            .map_err(|_| format!("{:?} isn't a challenge number or range (e.g. 10-20)", spec))
+    };
+
+    match spec.find('-') {
+        Some(index) => {
+            let low = parse(&spec[..index])?;
+            let high = parse(&spec[index + 1..])?;
+            if low > high {
+                Err(format!("The range {:?} is backwards", spec))
+            } else {
+                Ok((low, high))
+            }
+        }
+        None => {
+            let n = parse(spec)?;
+            Ok((n, n))
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_numbers_and_ranges() {
+        assert_eq!(parse_range("7"), Ok((7, 7)));
+        assert_eq!(parse_range("10-20"), Ok((10, 20)));
+        assert!(parse_range("20-10").is_err());
+        assert!(parse_range("ten").is_err());
+        assert!(parse_range("1-").is_err());
+    }
+
+    #[test]
+    fn select_by_number() {
+        let selection = Selection::new(vec!["3", "7", "10-20"], None).unwrap();
+
+        assert!(selection.matches(3, "Largest Prime factor"));
+        assert!(selection.matches(13, "Large Sum"));
+        assert!(!selection.matches(4, "Largest Palindrome Number"));
+        assert!(!selection.matches(67, "Maximum path sum II"));
+    }
+
+    #[test]
+    fn select_by_title() {
+        let selection = Selection::new(vec![], Some("prime")).unwrap();
+
+        assert!(selection.matches(3, "Largest Prime factor"));
+        assert!(!selection.matches(13, "Large Sum"));
+    }
+
+    #[test]
+    fn empty_selection_matches_everything() {
+        let selection = Selection::default();
+        assert!(selection.matches(67, "Maximum path sum II"));
+    }
+}
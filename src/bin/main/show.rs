@@ -0,0 +1,284 @@
+//! Printing a challenge's problem statement (`main show`), along with how it
+//! did the last time it was run.
+
+use ansi_term::Colour::*;
+use term_size;
+
+use answers::{Answers, Verdict};
+use header;
+use history::{self, Record, Run};
+use super::Challenge;
+
+
+/// Paragraphs of numbers with at least this many lines are treated as data
+/// (e.g. the grid in problem 11) rather than part of the statement. Smaller
+/// ones are usually worked examples.
+const DATA_BLOCK_LINES: usize = 5;
+
+
+/// How to show a challenge.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Settings {
+    /// The width to wrap the statement to.
+    pub width: usize,
+    /// Leave out any blocks of data embedded in the statement.
+    pub hide_data: bool,
+    pub hide_answers: bool,
+}
+
+impl Settings {
+    /// The width to use when none was asked for, the terminal's if we can
+    /// get it.
+    pub fn default_width() -> usize {
+        term_size::dimensions().map(|(width, _)| width).unwrap_or(80)
+    }
+}
+
+
+/// Print a challenge's full problem statement and its last result.
+pub fn show(challenge: &Challenge, settings: Settings) {
+    let header = &challenge.header;
+    let title = format!("Challenge {} - {}", header.number, header.title);
+    println!("{}", Green.bold().paint(title));
+    if !header.tags.is_empty() {
+        println!("Tags: {}", header.tags.join(", "));
+    }
+    if let Some(difficulty) = header.difficulty {
+        println!("Difficulty: {}%", difficulty);
+    }
+    println!("");
+
+    let docstring = challenge.full_docstring();
+    print!("{}", render(statement(&docstring, challenge.number), settings));
+
+    println!("{}", Blue.bold().paint("Last run:"));
+
+    let path = history::path();
+    let runs = match history::load(&path) {
+        Ok(runs) => runs,
+        // No history just means nothing has been recorded yet
+        Err(_) if !path.exists() => Vec::new(),
+        Err(msg) => {
+            eprintln!("{} {}", Yellow.bold().paint("Warning:"), msg);
+            Vec::new()
+        }
+    };
+
+    match last_result(&runs, challenge.number) {
+        Some((run, record)) => {
+            println!("{}", run.describe());
+            println!("Status: {}", record.status);
+            if let Some(ref answer) = record.answer {
+                let verdict = match Answers::load().check(challenge.number, answer) {
+                    Verdict::Correct => Green.paint("correct"),
+                    Verdict::Wrong { .. } => Red.paint("wrong"),
+                    Verdict::Unknown => Yellow.paint("unknown"),
+                };
+                if settings.hide_answers {
+                    println!("Answer: [hidden] ({})", verdict);
+                } else {
+                    println!("Answer: {} ({})", answer, verdict);
+                }
+            }
+            if record.status != "todo" {
+                println!("Running time: {:.3}ms", record.running_time_ms);
+            }
+        }
+        None => println!("{}", Yellow.paint("This challenge hasn't been run yet")),
+    }
+}
+
+
+/// The problem statement is everything after the header.
+fn statement(docstring: &str, number: usize) -> &str {
+    let (_, consumed, _) = header::parse(docstring, number);
+    &docstring[consumed..]
+}
+
+
+/// The most recent time a challenge was run normally (i.e. not benchmarked).
+fn last_result(runs: &[Run], number: usize) -> Option<(&Run, &Record)> {
+    runs.iter()
+        .rev()
+        .filter(|run| !run.bench)
+        .filter_map(|run| {
+            run.results
+                .iter()
+                .find(|record| record.number == number)
+                .map(|record| (run, record))
+        })
+        .next()
+}
+
+
+/// Lay out a problem statement, word-wrapping its prose to fit the width.
+///
+/// Paragraphs which look pre-formatted (indented lines, or blocks of numbers)
+/// are left alone.
+fn render(statement: &str, settings: Settings) -> String {
+    let mut rendered = String::new();
+
+    for paragraph in paragraphs(statement) {
+        if is_data(&paragraph) && settings.hide_data {
+            rendered.push_str(&format!("[{} lines of data hidden]\n", paragraph.len()));
+        } else if is_numbers(&paragraph) || paragraph.iter().any(|line| line.starts_with(' ')) {
+            for line in &paragraph {
+                rendered.push_str(line);
+                rendered.push('\n');
+            }
+        } else {
+            rendered.push_str(&wrap(&paragraph.join(" "), settings.width));
+        }
+        rendered.push('\n');
+    }
+
+    rendered
+}
+
+
+/// Split some text into paragraphs, skipping any blank lines.
+fn paragraphs(text: &str) -> Vec<Vec<&str>> {
+    let mut paragraphs = Vec::new();
+    let mut current = Vec::new();
+
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            if !current.is_empty() {
+                paragraphs.push(current);
+                current = Vec::new();
+            }
+        } else {
+            current.push(line);
+        }
+    }
+
+    if !current.is_empty() {
+        paragraphs.push(current);
+    }
+
+    paragraphs
+}
+
+
+fn is_data(paragraph: &[&str]) -> bool {
+    paragraph.len() >= DATA_BLOCK_LINES && is_numbers(paragraph)
+}
+
+fn is_numbers(paragraph: &[&str]) -> bool {
+    paragraph.iter()
+        .all(|line| line.chars().all(|c| c.is_digit(10) || c == ' ' || c == ','))
+}
+
+
+/// Greedily fill lines with as many words as will fit, measuring them in
+/// characters rather than bytes.
+fn wrap(text: &str, width: usize) -> String {
+    let mut wrapped = String::new();
+    let mut line_length = 0;
+
+    for word in text.split_whitespace() {
+        let word_length = word.chars().count();
+        if line_length > 0 && line_length + 1 + word_length > width {
+            wrapped.push('\n');
+            line_length = 0;
+        }
+        if line_length > 0 {
+            wrapped.push(' ');
+            line_length += 1;
+        }
+
+        wrapped.push_str(word);
+        line_length += word_length;
+    }
+
+    wrapped.push('\n');
+    wrapped
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STATEMENT: &'static str = "Work out the first ten digits of the sum of the following
+one-hundred 50-digit numbers.
+
+37107287533902102798797998220837590246510135740250
+46376937677490009712648124896970078050417018260538
+74324986199524741059474233309513058123726617309629
+91942213363574161572522430563301811072406154908250
+23067588207539346171171980310421047513778063246676
+
+3
+7 4
+
+ 1: 1
+10: 1,2,5,10";
+
+    fn settings(hide_data: bool) -> Settings {
+        Settings {
+            width: 30,
+            hide_data: hide_data,
+            hide_answers: false,
+        }
+    }
+
+    #[test]
+    fn wrap_prose_but_not_data() {
+        let got = render(STATEMENT, settings(false));
+
+        assert!(got.starts_with("Work out the first ten digits\nof the sum of the following\n\
+                                 one-hundred 50-digit numbers.\n\n\
+                                 37107287533902102798797998220837590246510135740250\n"));
+        assert!(got.ends_with("\n3\n7 4\n\n 1: 1\n10: 1,2,5,10\n\n"));
+    }
+
+    #[test]
+    fn wrap_by_characters_not_bytes() {
+        assert_eq!(wrap("π ≈ 3.14159 and √2 ≈ 1.41421", 11),
+                   "π ≈ 3.14159\nand √2 ≈\n1.41421\n");
+    }
+
+    #[test]
+    fn hide_data_blocks() {
+        let got = render(STATEMENT, settings(true));
+
+        assert!(got.contains("numbers.\n\n[5 lines of data hidden]\n\n3\n7 4\n\n 1: 1\n"));
+        assert!(!got.contains("3710728753"));
+    }
+
+    #[test]
+    fn the_statement_comes_after_the_header() {
+        assert_eq!(statement("Challenge 1 - Multiples\nTags: maths\n\nIf we list...\n", 1),
+                   "If we list...\n");
+        assert_eq!(statement("Challenge 1 - Multiples\nIf we list...\n\nFind the sum", 1),
+                   "If we list...\n\nFind the sum");
+    }
+
+    #[test]
+    fn find_the_last_normal_run() {
+        let run = |bench: bool, answer: &str| {
+            Run {
+                timestamp: String::from("2017-10-01T12:00:00Z"),
+                commit: None,
+                dirty: false,
+                tag: None,
+                release: false,
+                in_process: false,
+                bench: bench,
+                results: vec![Record {
+                                  number: 1,
+                                  status: String::from("success"),
+                                  answer: Some(String::from(answer)),
+                                  running_time_ms: 1.0,
+                              }],
+            }
+        };
+        let runs = vec![run(false, "first"), run(false, "second"), run(true, "bench")];
+
+        let (_, record) = last_result(&runs, 1).unwrap();
+
+        assert_eq!(record.answer, Some(String::from("second")));
+        assert!(last_result(&runs, 2).is_none());
+    }
+}
@@ -0,0 +1,293 @@
+//! Watch mode (`main watch`), which rebuilds and re-runs challenges whenever
+//! their source changes.
+//!
+//! Only the challenges affected by a change are re-run. Editing a challenge
+//! binary (or one of the data files it reads) re-runs just that challenge,
+//! while anything which touches the library re-runs all of them. Changes to
+//! the runner itself are ignored, seeing as it can't rebuild itself.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+
+use euler::data::Entry;
+
+
+/// Which challenges need re-running after some files changed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Affected {
+    /// None of the changes matter (e.g. someone edited the README).
+    Nothing,
+    /// Just these challenges, by number.
+    Challenges(BTreeSet<usize>),
+    /// The library or data manifest changed, so every challenge is suspect.
+    Everything,
+}
+
+
+/// Work out which challenges are affected by changes to some files.
+///
+/// `data` is the data manifest, used to find out which challenges read a
+/// particular data file.
+pub fn affected(root: &Path, changed: &[PathBuf], data: &[Entry]) -> Affected {
+    let challenge = Regex::new(r"^challenge_(\d+)\.rs$").unwrap();
+    let mut numbers = BTreeSet::new();
+
+    for path in changed {
+        let relative = match path.strip_prefix(root) {
+            Ok(relative) => relative,
+            Err(_) => continue,
+        };
+        let filename = match relative.file_name().and_then(|name| name.to_str()) {
+            Some(filename) => filename,
+            None => continue,
+        };
+
+        if relative.starts_with("src") {
+            if relative.starts_with("src/bin/main") || !filename.ends_with(".rs") {
+                continue;
+            }
+
+            let number = challenge.captures(filename)
+                .and_then(|caps| caps.at(1))
+                .and_then(|n| n.parse().ok());
+            match number {
+                Some(n) if relative.parent() == Some(Path::new("src/bin")) => {
+                    numbers.insert(n);
+                }
+                // Everything else is part of the library
+                _ => return Affected::Everything,
+            }
+        } else if relative.starts_with("data") {
+            if filename == "manifest.toml" {
+                return Affected::Everything;
+            }
+            numbers.extend(data.iter()
+                .filter(|entry| entry.file == filename)
+                .map(|entry| entry.problem));
+        }
+    }
+
+    if numbers.is_empty() {
+        Affected::Nothing
+    } else {
+        Affected::Challenges(numbers)
+    }
+}
+
+
+#[cfg(target_os = "linux")]
+pub use self::linux::watch;
+
+#[cfg(not(target_os = "linux"))]
+pub fn watch(_selection: &::selection::Selection, _opts: ::Options) -> Result<(), String> {
+    Err(String::from("Watch mode uses inotify, so it's only supported on Linux"))
+}
+
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::collections::HashMap;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::thread;
+    use std::time::Duration;
+
+    use ansi_term::Colour::*;
+    use inotify::{EventMask, Inotify, WatchDescriptor, WatchMask};
+
+    use euler::data;
+
+    use answers::Answers;
+    use reporter;
+    use selection::Selection;
+    use super::{affected, Affected};
+    use super::super::{execute_binaries, get_binaries, Challenge, Options, PACKAGE_ROOT};
+
+    /// How long to wait for things to settle down after a change, so saving
+    /// several files at once only triggers one rebuild.
+    const SETTLE_TIME: u64 = 200;
+
+    /// Run the selected challenges, then keep re-running them as they're
+    /// changed. This only returns if something goes wrong.
+    pub fn watch(selection: &Selection, opts: Options) -> Result<(), String> {
+        let root = Path::new(PACKAGE_ROOT);
+        let mut inotify = Inotify::init().map_err(|e| format!("Unable to start inotify ({})", e))?;
+
+        let mut directories = HashMap::new();
+        for top in &["src", "data"] {
+            add_watches(&mut inotify, &mut directories, &root.join(top))?;
+        }
+
+        run(selected(selection, |_| true), opts);
+
+        loop {
+            let changed = wait_for_changes(&mut inotify, &mut directories)?;
+            let manifest = data::manifest().unwrap_or_default();
+
+            let challenges = match affected(root, &changed, &manifest) {
+                Affected::Nothing => continue,
+                Affected::Everything => selected(selection, |_| true),
+                Affected::Challenges(numbers) => {
+                    selected(selection, |c| numbers.contains(&c.number))
+                }
+            };
+
+            if !challenges.is_empty() {
+                let names: Vec<&str> = challenges.iter().map(|c| c.name.as_str()).collect();
+                println!("{} re-running {}\n",
+                         Purple.bold().paint("Change detected,"),
+                         names.join(", "));
+                run(challenges, opts);
+            }
+        }
+    }
+
+    fn selected<F>(selection: &Selection, filter: F) -> Vec<Challenge>
+        where F: Fn(&Challenge) -> bool
+    {
+        get_binaries()
+            .into_iter()
+            .filter(|c| selection.matches(c.number, &c.title()) && filter(c))
+            .collect()
+    }
+
+    fn run(challenges: Vec<Challenge>, opts: Options) {
+        let mut reporter = reporter::reporter(opts.format, &opts);
+
+        if let Err(msg) = execute_binaries(challenges, &Answers::load(), opts, &mut *reporter) {
+            eprintln!("{} {}", Red.bold().paint("Error:"), msg);
+        }
+
+        println!("{}", Purple.paint("Waiting for changes..."));
+    }
+
+    /// Watch a directory and everything inside it.
+    fn add_watches(inotify: &mut Inotify,
+                   directories: &mut HashMap<WatchDescriptor, PathBuf>,
+                   top: &Path)
+                   -> Result<(), String> {
+        for dir in directories_under(top) {
+            let wd = inotify.add_watch(&dir,
+                           WatchMask::CLOSE_WRITE | WatchMask::MOVED_TO |
+                           WatchMask::CREATE | WatchMask::DELETE)
+                .map_err(|e| format!("Unable to watch {} ({})", dir.display(), e))?;
+            directories.insert(wd, dir);
+        }
+
+        Ok(())
+    }
+
+    /// Block until something changes, returning the files which changed.
+    ///
+    /// Any directories created in the meantime get watched too, so files
+    /// added to them later aren't missed.
+    fn wait_for_changes(inotify: &mut Inotify,
+                        directories: &mut HashMap<WatchDescriptor, PathBuf>)
+                        -> Result<Vec<PathBuf>, String> {
+        let mut buffer = [0; 4096];
+        let mut changed = Vec::new();
+
+        let mut blocking = true;
+        loop {
+            let events = if blocking {
+                inotify.read_events_blocking(&mut buffer)
+            } else {
+                inotify.read_events(&mut buffer)
+            };
+            let events = events.map_err(|e| format!("Unable to read file events ({})", e))?;
+
+            let before = changed.len();
+            let mut new_directories = Vec::new();
+            for event in events {
+                if let (Some(dir), Some(name)) = (directories.get(&event.wd), event.name) {
+                    let path = dir.join(name);
+                    if event.mask.contains(EventMask::ISDIR) &&
+                       event.mask.intersects(EventMask::CREATE | EventMask::MOVED_TO) {
+                        new_directories.push(path.clone());
+                    }
+                    changed.push(path);
+                }
+            }
+
+            for dir in new_directories {
+                // It may have been deleted again already, which is fine
+                if let Err(msg) = add_watches(inotify, directories, &dir) {
+                    if dir.exists() {
+                        return Err(msg);
+                    }
+                }
+            }
+
+            if !blocking && changed.len() == before {
+                return Ok(changed);
+            }
+
+            blocking = false;
+            thread::sleep(Duration::from_millis(SETTLE_TIME));
+        }
+    }
+
+    /// A directory and every directory inside it.
+    fn directories_under(dir: &Path) -> Vec<PathBuf> {
+        let mut found = vec![dir.to_path_buf()];
+
+        if let Ok(entries) = fs::read_dir(dir) {
+            for entry in entries.filter_map(|entry| entry.ok()) {
+                if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                    found.extend(directories_under(&entry.path()));
+                }
+            }
+        }
+
+        found
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use euler::data::Format;
+
+    fn check(changed: &[&str]) -> Affected {
+        let root = Path::new("/euler");
+        let manifest = vec![Entry {
+                                problem: 67,
+                                file: String::from("problem_067_triangle.txt"),
+                                format: Format::Triangle,
+                                sha256: String::new(),
+                            }];
+        let changed: Vec<PathBuf> = changed.iter().map(|path| root.join(path)).collect();
+
+        affected(root, &changed, &manifest)
+    }
+
+    fn challenges(numbers: &[usize]) -> Affected {
+        Affected::Challenges(numbers.iter().cloned().collect())
+    }
+
+    #[test]
+    fn only_rerun_changed_challenges() {
+        assert_eq!(check(&["src/bin/challenge_3.rs", "src/bin/challenge_10.rs"]),
+                   challenges(&[3, 10]));
+        assert_eq!(check(&["data/problem_067_triangle.txt"]), challenges(&[67]));
+    }
+
+    #[test]
+    fn library_changes_rerun_everything() {
+        assert_eq!(check(&["src/bin/challenge_3.rs", "src/primes.rs"]), Affected::Everything);
+        assert_eq!(check(&["src/lib.rs"]), Affected::Everything);
+        assert_eq!(check(&["data/manifest.toml"]), Affected::Everything);
+    }
+
+    #[test]
+    fn ignore_everything_else() {
+        assert_eq!(check(&["src/bin/main/main.rs",
+                           "src/bin/.challenge_3.rs.swp",
+                           "README.md",
+                           "data/notes.txt"]),
+                   Affected::Nothing);
+    }
+}
@@ -0,0 +1,720 @@
+//! Typed loaders for the input files kept in the `data/` directory.
+//!
+//! Each loader takes the name of a file inside `data/` and parses it into
+//! something a challenge can use directly. Any problems are reported with
+//! the offending file and (where it makes sense) line number.
+//!
+//! Most of the time you'll want to use `load()` instead, which looks the
+//! problem number up in `data/manifest.toml` to find the right file, checks
+//! it hasn't been corrupted and parses it into the requested type.
+//!
+//! ```rust,no_run
+//! # use euler::data;
+//! let triangle: Vec<Vec<usize>> = data::load(67).unwrap();
+//! ```
+//!
+//! Files are normally read from the crate's `data/` directory at run time,
+//! although you can point the loaders somewhere else by setting the
+//! `EULER_DATA_DIR` environment variable. Compiling with the `embed-data`
+//! feature bakes every file into the binary instead, so it keeps working
+//! after being copied somewhere else (`EULER_DATA_DIR` still takes
+//! precedence when it's set).
+
+use std::env;
+use std::error;
+use std::fmt::{self, Display, Formatter};
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::result;
+use std::str::FromStr;
+
+use num::BigUint;
+use sha2::{Digest, Sha256};
+use toml;
+
+use grid::Grid;
+
+
+/// The directory all data files are kept in.
+const DATA_DIR: &'static str = concat!(env!("CARGO_MANIFEST_DIR"), "/data");
+
+/// The environment variable used to override `DATA_DIR`.
+pub const DATA_DIR_VARIABLE: &'static str = "EULER_DATA_DIR";
+
+/// Every data file, baked into the binary when the `embed-data` feature is
+/// enabled. New files need to be added here as well as to the manifest.
+#[cfg(feature = "embed-data")]
+static EMBEDDED: &'static [(&'static str, &'static str)] =
+    &[("manifest.toml", include_str!("../data/manifest.toml")),
+      ("problem_8_number.txt", include_str!("../data/problem_8_number.txt")),
+      ("problem_13_numbers.txt", include_str!("../data/problem_13_numbers.txt")),
+      ("problem_067_triangle.txt", include_str!("../data/problem_067_triangle.txt"))];
+
+/// The file (inside `DATA_DIR`) which says which problem uses which file.
+const MANIFEST: &'static str = "manifest.toml";
+
+/// A `Result` whose error type is a data loading `Error`.
+pub type Result<T> = result::Result<T, Error>;
+
+
+/// An error encountered while loading a data file.
+#[derive(Debug)]
+pub struct Error {
+    path: PathBuf,
+    line: Option<usize>,
+    kind: ErrorKind,
+}
+
+/// The different things which can go wrong when loading a data file.
+#[derive(Debug)]
+pub enum ErrorKind {
+    /// The file doesn't exist.
+    Missing,
+    /// The file couldn't be read.
+    Io(io::Error),
+    /// The file's contents weren't in the expected format.
+    Parse(String),
+    /// The manifest doesn't have an entry for this problem.
+    UnknownProblem(usize),
+    /// The manifest itself is malformed.
+    Manifest(String),
+    /// The file's checksum doesn't match the one in the manifest.
+    Corrupted {
+        /// The checksum listed in the manifest.
+        expected: String,
+        /// The checksum of the file on disk.
+        actual: String,
+    },
+    /// The file was loaded as a different format to the one in the
+    /// manifest.
+    WrongFormat {
+        /// The format listed in the manifest.
+        expected: Format,
+        /// The format which was requested.
+        requested: Format,
+    },
+}
+
+impl Error {
+    fn new(path: &Path, line: Option<usize>, kind: ErrorKind) -> Error {
+        Error {
+            path: path.to_path_buf(),
+            line: line,
+            kind: kind,
+        }
+    }
+
+    /// The file being loaded.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The line the error was found on, if it can be pinned to one.
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// What went wrong.
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.path.display())?;
+        if let Some(line) = self.line {
+            write!(f, ":{}", line)?;
+        }
+
+        match self.kind {
+            ErrorKind::Missing => write!(f, ": file not found"),
+            ErrorKind::Io(ref e) => write!(f, ": {}", e),
+            ErrorKind::Parse(ref msg) |
+            ErrorKind::Manifest(ref msg) => write!(f, ": {}", msg),
+            ErrorKind::UnknownProblem(n) => write!(f, ": no data registered for problem {}", n),
+            ErrorKind::Corrupted { ref expected, ref actual } => {
+                write!(f,
+                       ": checksum mismatch, expected {} but found {}",
+                       expected,
+                       actual)
+            }
+            ErrorKind::WrongFormat { expected, requested } => {
+                write!(f,
+                       ": the file contains {} but was loaded as {}",
+                       expected,
+                       requested)
+            }
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        match self.kind {
+            ErrorKind::Missing => "The data file doesn't exist",
+            ErrorKind::Io(_) => "Unable to read the data file",
+            ErrorKind::Parse(ref msg) |
+            ErrorKind::Manifest(ref msg) => msg,
+            ErrorKind::UnknownProblem(_) => "No data registered for this problem",
+            ErrorKind::Corrupted { .. } => "The data file's checksum doesn't match",
+            ErrorKind::WrongFormat { .. } => "The data file was loaded in the wrong format",
+        }
+    }
+}
+
+
+/// A parse error (and the line it occurred on) which hasn't been attached to
+/// a file yet.
+#[doc(hidden)]
+pub type ParseError = (Option<usize>, String);
+
+
+/// Get the full path to a file in the `data/` directory.
+///
+/// This is inside `EULER_DATA_DIR` if the variable is set, otherwise the
+/// crate's own `data/` directory.
+pub fn path(name: &str) -> PathBuf {
+    path_in(overridden_dir(), name)
+}
+
+/// Read the entire contents of a data file.
+pub fn read(name: &str) -> Result<String> {
+    read_from(overridden_dir(), name)
+}
+
+/// The directory `EULER_DATA_DIR` points at, if it's set.
+fn overridden_dir() -> Option<PathBuf> {
+    env::var_os(DATA_DIR_VARIABLE).map(PathBuf::from)
+}
+
+fn path_in(dir: Option<PathBuf>, name: &str) -> PathBuf {
+    dir.unwrap_or_else(|| PathBuf::from(DATA_DIR)).join(name)
+}
+
+/// Read a data file from `dir`, or from the embedded copies when no
+/// directory was given.
+#[cfg(feature = "embed-data")]
+fn read_from(dir: Option<PathBuf>, name: &str) -> Result<String> {
+    if let Some(dir) = dir {
+        return read_from_disk(&dir.join(name));
+    }
+
+    EMBEDDED.iter()
+        .find(|&&(embedded_name, _)| embedded_name == name)
+        .map(|&(_, contents)| contents.to_string())
+        .ok_or_else(|| Error::new(&path_in(None, name), None, ErrorKind::Missing))
+}
+
+/// Read a data file from `dir`, or from the crate's `data/` directory when
+/// no directory was given.
+#[cfg(not(feature = "embed-data"))]
+fn read_from(dir: Option<PathBuf>, name: &str) -> Result<String> {
+    read_from_disk(&path_in(dir, name))
+}
+
+fn read_from_disk(path: &Path) -> Result<String> {
+    debug!("Reading from {}", path.display());
+
+    let mut contents = String::new();
+    File::open(path)
+        .and_then(|mut f| f.read_to_string(&mut contents))
+        .map_err(|e| {
+            let kind = if e.kind() == io::ErrorKind::NotFound {
+                ErrorKind::Missing
+            } else {
+                ErrorKind::Io(e)
+            };
+            Error::new(path, None, kind)
+        })?;
+
+    Ok(contents)
+}
+
+/// Load a file containing a long string of digits (possibly broken over
+/// several lines).
+pub fn digits(name: &str) -> Result<Vec<u64>> {
+    load_file(name, parse_digits)
+}
+
+/// Load a file containing one big number per line.
+pub fn big_numbers(name: &str) -> Result<Vec<BigUint>> {
+    load_file(name, parse_big_numbers)
+}
+
+/// Load a triangle of numbers, where the `n`'th line contains `n` numbers
+/// separated by whitespace.
+pub fn triangle(name: &str) -> Result<Vec<Vec<usize>>> {
+    load_file(name, parse_triangle)
+}
+
+/// Load a rectangular grid of whitespace separated numbers.
+pub fn grid(name: &str) -> Result<Grid<usize>> {
+    load_file(name, parse_grid)
+}
+
+/// Load a comma separated list of quoted words (e.g. `"MARY","PATRICIA"`).
+pub fn words(name: &str) -> Result<Vec<String>> {
+    load_file(name, parse_words)
+}
+
+
+/// The formats a data file can be in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    /// A long string of digits (see `digits()`).
+    Digits,
+    /// One big number per line (see `big_numbers()`).
+    BigNumbers,
+    /// A triangle of numbers (see `triangle()`).
+    Triangle,
+    /// A rectangular grid of numbers (see `grid()`).
+    Grid,
+    /// A list of quoted words (see `words()`).
+    Words,
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let name = match *self {
+            Format::Digits => "digits",
+            Format::BigNumbers => "big-numbers",
+            Format::Triangle => "triangle",
+            Format::Grid => "grid",
+            Format::Words => "words",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> result::Result<Format, String> {
+        match s {
+            "digits" => Ok(Format::Digits),
+            "big-numbers" => Ok(Format::BigNumbers),
+            "triangle" => Ok(Format::Triangle),
+            "grid" => Ok(Format::Grid),
+            "words" => Ok(Format::Words),
+            other => Err(format!("Unknown format, {:?}", other)),
+        }
+    }
+}
+
+
+/// Something which can be loaded from a data file using `load()`.
+pub trait Load: Sized {
+    /// The format this type is stored as.
+    fn format() -> Format;
+
+    #[doc(hidden)]
+    fn parse(contents: &str) -> result::Result<Self, ParseError>;
+}
+
+impl Load for Vec<u64> {
+    fn format() -> Format {
+        Format::Digits
+    }
+
+    fn parse(contents: &str) -> result::Result<Self, ParseError> {
+        parse_digits(contents)
+    }
+}
+
+impl Load for Vec<BigUint> {
+    fn format() -> Format {
+        Format::BigNumbers
+    }
+
+    fn parse(contents: &str) -> result::Result<Self, ParseError> {
+        parse_big_numbers(contents)
+    }
+}
+
+impl Load for Vec<Vec<usize>> {
+    fn format() -> Format {
+        Format::Triangle
+    }
+
+    fn parse(contents: &str) -> result::Result<Self, ParseError> {
+        parse_triangle(contents)
+    }
+}
+
+impl Load for Grid<usize> {
+    fn format() -> Format {
+        Format::Grid
+    }
+
+    fn parse(contents: &str) -> result::Result<Self, ParseError> {
+        parse_grid(contents)
+    }
+}
+
+impl Load for Vec<String> {
+    fn format() -> Format {
+        Format::Words
+    }
+
+    fn parse(contents: &str) -> result::Result<Self, ParseError> {
+        parse_words(contents)
+    }
+}
+
+
+/// A single problem's entry in the manifest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    /// The problem number.
+    pub problem: usize,
+    /// The name of the file inside the `data/` directory.
+    pub file: String,
+    /// The format the file is stored in.
+    pub format: Format,
+    /// The file's expected SHA-256 checksum, as a hex string.
+    pub sha256: String,
+}
+
+
+/// Read the manifest, returning every entry sorted by problem number.
+pub fn manifest() -> Result<Vec<Entry>> {
+    let contents = read(MANIFEST)?;
+    parse_manifest(&contents)
+        .map_err(|msg| Error::new(&path(MANIFEST), None, ErrorKind::Manifest(msg)))
+}
+
+/// Look up a problem's data file in the manifest, make sure it hasn't been
+/// corrupted, and parse it.
+pub fn load<T: Load>(problem: usize) -> Result<T> {
+    let entry = manifest()?
+        .into_iter()
+        .find(|entry| entry.problem == problem)
+        .ok_or_else(|| {
+            Error::new(&path(MANIFEST), None, ErrorKind::UnknownProblem(problem))
+        })?;
+
+    if entry.format != T::format() {
+        let kind = ErrorKind::WrongFormat {
+            expected: entry.format,
+            requested: T::format(),
+        };
+        return Err(Error::new(&path(&entry.file), None, kind));
+    }
+
+    let contents = read(&entry.file)?;
+    let actual = sha256(contents.as_bytes());
+    if actual != entry.sha256.to_lowercase() {
+        let kind = ErrorKind::Corrupted {
+            expected: entry.sha256,
+            actual: actual,
+        };
+        return Err(Error::new(&path(&entry.file), None, kind));
+    }
+
+    T::parse(&contents)
+        .map_err(|(line, msg)| Error::new(&path(&entry.file), line, ErrorKind::Parse(msg)))
+}
+
+/// Calculate the SHA-256 checksum of some bytes as a lowercase hex string.
+pub fn sha256(bytes: &[u8]) -> String {
+    // Hex-encode by hand rather than relying on the digest's `LowerHex` impl,
+    // which not every version of sha2 provides
+    Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+
+fn load_file<T, F>(name: &str, parser: F) -> Result<T>
+    where F: Fn(&str) -> result::Result<T, ParseError>
+{
+    let contents = read(name)?;
+    parser(&contents).map_err(|(line, msg)| Error::new(&path(name), line, ErrorKind::Parse(msg)))
+}
+
+/// Get the non-blank lines in some text, along with their
+/// (1-based) line numbers.
+fn numbered_lines(contents: &str) -> Vec<(usize, &str)> {
+    contents.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|&(_, line)| !line.is_empty())
+        .collect()
+}
+
+fn parse_numbers(line_number: usize, line: &str) -> result::Result<Vec<usize>, ParseError> {
+    line.split_whitespace()
+        .map(|word| {
+            word.parse()
+                .map_err(|e| (Some(line_number), format!("Unable to parse {:?} ({})", word, e)))
+        })
+        .collect()
+}
+
+fn parse_digits(contents: &str) -> result::Result<Vec<u64>, ParseError> {
+    let mut digits = Vec::new();
+
+    for (line_number, line) in numbered_lines(contents) {
+        for c in line.chars() {
+            match c.to_digit(10) {
+                Some(d) => digits.push(d as u64),
+                None => return Err((Some(line_number), format!("{:?} isn't a digit", c))),
+            }
+        }
+    }
+
+    Ok(digits)
+}
+
+fn parse_big_numbers(contents: &str) -> result::Result<Vec<BigUint>, ParseError> {
+    numbered_lines(contents)
+        .into_iter()
+        .map(|(line_number, line)| {
+            line.parse()
+                .map_err(|_| (Some(line_number), format!("{:?} isn't a valid number", line)))
+        })
+        .collect()
+}
+
+fn parse_triangle(contents: &str) -> result::Result<Vec<Vec<usize>>, ParseError> {
+    let mut triangle = Vec::new();
+
+    for (line_number, line) in numbered_lines(contents) {
+        let row = parse_numbers(line_number, line)?;
+        if row.len() != triangle.len() + 1 {
+            return Err((Some(line_number),
+                        format!("Expected {} numbers but found {}",
+                                triangle.len() + 1,
+                                row.len())));
+        }
+        triangle.push(row);
+    }
+
+    Ok(triangle)
+}
+
+fn parse_grid(contents: &str) -> result::Result<Grid<usize>, ParseError> {
+    let mut rows: Vec<Vec<usize>> = Vec::new();
+
+    for (line_number, line) in numbered_lines(contents) {
+        let row = parse_numbers(line_number, line)?;
+        if let Some(first) = rows.first() {
+            if row.len() != first.len() {
+                return Err((Some(line_number),
+                            format!("Expected {} numbers but found {}", first.len(), row.len())));
+            }
+        }
+        rows.push(row);
+    }
+
+    Grid::from_rows(rows).map_err(|msg| (None, msg))
+}
+
+fn parse_words(contents: &str) -> result::Result<Vec<String>, ParseError> {
+    let mut words = Vec::new();
+
+    for (line_number, line) in numbered_lines(contents) {
+        for word in line.split(',').map(|w| w.trim()).filter(|w| !w.is_empty()) {
+            if word.len() < 2 || !word.starts_with('"') || !word.ends_with('"') {
+                return Err((Some(line_number), format!("{} isn't a quoted word", word)));
+            }
+            words.push(word[1..word.len() - 1].to_string());
+        }
+    }
+
+    Ok(words)
+}
+
+
+fn parse_manifest(contents: &str) -> result::Result<Vec<Entry>, String> {
+    let value: toml::Value = contents.parse().map_err(|e| format!("Invalid TOML ({})", e))?;
+    let table = value.as_table().ok_or("The manifest should be a table")?;
+
+    let mut entries = Vec::new();
+    for (key, value) in table {
+        let problem = key.parse()
+            .map_err(|_| format!("{:?} isn't a problem number", key))?;
+        let get = |field: &str| {
+            value.get(field)
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| format!("Problem {} is missing the \"{}\" field", problem, field))
+        };
+
+        entries.push(Entry {
+            problem: problem,
+            file: get("file")?.to_string(),
+            format: get("format")?.parse()?,
+            sha256: get("sha256")?.to_string(),
+        });
+    }
+
+    entries.sort_by_key(|entry| entry.problem);
+    Ok(entries)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::Write;
+    use std::process;
+
+    use super::*;
+    use super::{parse_digits, parse_grid, parse_manifest, parse_triangle, parse_words};
+
+    #[test]
+    fn digits_can_span_multiple_lines() {
+        let src = "123\n456\n\n7\n";
+        let got = parse_digits(src).unwrap();
+        assert_eq!(got, vec![1, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn digits_reject_other_characters() {
+        let got = parse_digits("123\n4x6\n");
+        assert_eq!(got, Err((Some(2), String::from("'x' isn't a digit"))));
+    }
+
+    #[test]
+    fn triangle_rows_must_grow_by_one() {
+        assert_eq!(parse_triangle("3\n7 4\n").unwrap(), vec![vec![3], vec![7, 4]]);
+
+        let got = parse_triangle("3\n7 4\n2 4\n");
+        assert_eq!(got.unwrap_err().0, Some(3));
+    }
+
+    #[test]
+    fn grids_must_be_rectangular() {
+        let grid = parse_grid("01 02\n03 04\n").unwrap();
+        assert_eq!(grid.get(1, 0), Some(&3));
+
+        let got = parse_grid("01 02\n03\n");
+        assert_eq!(got.unwrap_err().0, Some(2));
+    }
+
+    #[test]
+    fn parse_quoted_words() {
+        let got = parse_words("\"MARY\",\"PATRICIA\",\"LINDA\"").unwrap();
+        assert_eq!(got, vec!["MARY", "PATRICIA", "LINDA"]);
+
+        assert!(parse_words("\"MARY\",PATRICIA").is_err());
+    }
+
+    #[test]
+    fn load_the_existing_data_files() {
+        assert_eq!(digits("problem_8_number.txt").unwrap().len(), 1000);
+        assert_eq!(big_numbers("problem_13_numbers.txt").unwrap().len(), 100);
+        assert_eq!(triangle("problem_067_triangle.txt").unwrap().len(), 100);
+    }
+
+    #[test]
+    fn errors_mention_the_file() {
+        let err = digits("this_file_does_not_exist.txt").unwrap_err();
+        assert!(err.to_string().contains("this_file_does_not_exist.txt"));
+    }
+
+    #[test]
+    fn parse_a_manifest_entry() {
+        let src = "[67]\nfile = \"triangle.txt\"\nformat = \"triangle\"\nsha256 = \"abcd\"\n";
+        let should_be = vec![Entry {
+                                 problem: 67,
+                                 file: String::from("triangle.txt"),
+                                 format: Format::Triangle,
+                                 sha256: String::from("abcd"),
+                             }];
+
+        let got = parse_manifest(src).unwrap();
+        assert_eq!(got, should_be);
+    }
+
+    #[test]
+    fn manifest_entries_need_every_field() {
+        let src = "[67]\nfile = \"triangle.txt\"\n";
+        assert!(parse_manifest(src).is_err());
+    }
+
+    #[test]
+    fn sha256_known_values() {
+        assert_eq!(sha256(b""),
+                   "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(sha256(b"abc"),
+                   "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    }
+
+    #[test]
+    fn every_manifest_entry_is_valid() {
+        for entry in manifest().unwrap() {
+            let contents = read(&entry.file).unwrap();
+            assert_eq!(sha256(contents.as_bytes()), entry.sha256, "{}", entry.file);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "embed-data")]
+    fn every_manifest_entry_is_embedded() {
+        let embedded = |name: &str| {
+            EMBEDDED.iter()
+                .find(|&&(embedded_name, _)| embedded_name == name)
+                .map(|&(_, contents)| contents)
+        };
+
+        let manifest = parse_manifest(embedded(MANIFEST).unwrap()).unwrap();
+        for entry in manifest {
+            let contents = embedded(&entry.file).expect(&entry.file);
+            assert_eq!(sha256(contents.as_bytes()), entry.sha256, "{}", entry.file);
+        }
+    }
+
+    /// A scratch directory which is deleted afterwards.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> TempDir {
+            let name = format!("euler-data-{}", process::id());
+            let dir = env::temp_dir().join(name);
+            fs::create_dir_all(&dir).unwrap();
+            TempDir(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn the_data_directory_can_be_overridden() {
+        let dir = TempDir::new();
+        File::create(dir.0.join("only_in_override.txt"))
+            .and_then(|mut f| f.write_all(b"overridden"))
+            .unwrap();
+
+        let got = read_from(Some(dir.0.clone()), "only_in_override.txt").unwrap();
+
+        assert_eq!(got, "overridden");
+        assert!(read_from(None, "only_in_override.txt").is_err());
+        // Nothing falls back to the normal files once it's overridden
+        assert!(read_from(Some(dir.0.clone()), MANIFEST).is_err());
+        assert_eq!(path_in(Some(dir.0.clone()), MANIFEST), dir.0.join(MANIFEST));
+    }
+
+    #[test]
+    fn load_by_problem_number() {
+        let triangle: Vec<Vec<usize>> = load(67).unwrap();
+        assert_eq!(triangle.len(), 100);
+    }
+
+    #[test]
+    fn loading_unknown_problems_or_the_wrong_format_fails() {
+        match load::<Vec<u64>>(1).unwrap_err().kind() {
+            &ErrorKind::UnknownProblem(1) => {}
+            other => panic!("Unexpected error: {:?}", other),
+        }
+
+        match load::<Vec<u64>>(67).unwrap_err().kind() {
+            &ErrorKind::WrongFormat { .. } => {}
+            other => panic!("Unexpected error: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,272 @@
+//! A rectangular grid of values and helpers for scanning it along straight
+//! lines (rows, columns and both diagonals).
+
+use std::ops::{Add, Mul};
+
+use num::{One, Zero};
+
+
+/// The directions a line can run in, starting from its first cell.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Direction {
+    /// Along a row, from left to right.
+    Right,
+    /// Down a column, from top to bottom.
+    Down,
+    /// Diagonally towards the bottom right.
+    DownRight,
+    /// Diagonally towards the bottom left.
+    DownLeft,
+}
+
+impl Direction {
+    /// Every direction a line can be scanned in.
+    pub fn all() -> [Direction; 4] {
+        [Direction::Right, Direction::Down, Direction::DownRight, Direction::DownLeft]
+    }
+
+    /// The (row, column) offset taken for each step in this direction.
+    fn step(&self) -> (isize, isize) {
+        match *self {
+            Direction::Right => (0, 1),
+            Direction::Down => (1, 0),
+            Direction::DownRight => (1, 1),
+            Direction::DownLeft => (1, -1),
+        }
+    }
+}
+
+
+/// A straight run of cells in a grid, along with the value it reduced to.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Line<T> {
+    /// The row of the line's first cell.
+    pub row: usize,
+    /// The column of the line's first cell.
+    pub column: usize,
+    /// Which way the line runs from its first cell.
+    pub direction: Direction,
+    /// The result of combining every cell in the line.
+    pub value: T,
+}
+
+
+/// A rectangular grid of values, stored in row-major order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Create a grid from a list of rows, making sure every row is the same
+    /// length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, String> {
+        let height = rows.len();
+        let width = rows.first().map(|row| row.len()).unwrap_or(0);
+
+        let mut cells = Vec::with_capacity(width * height);
+        for (i, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                return Err(format!("Row {} has {} elements but expected {}",
+                                   i,
+                                   row.len(),
+                                   width));
+            }
+            cells.extend(row);
+        }
+
+        Ok(Grid {
+            width: width,
+            height: height,
+            cells: cells,
+        })
+    }
+
+    /// The number of columns in the grid.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The number of rows in the grid.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Get the value at a particular row and column.
+    pub fn get(&self, row: usize, column: usize) -> Option<&T> {
+        if row < self.height && column < self.width {
+            self.cells.get(row * self.width + column)
+        } else {
+            None
+        }
+    }
+
+    /// Get the cells in a line of `length` elements starting at `(row,
+    /// column)`, or `None` if the line would leave the grid.
+    pub fn line(&self,
+                row: usize,
+                column: usize,
+                direction: Direction,
+                length: usize)
+                -> Option<Vec<&T>> {
+        if length == 0 {
+            return None;
+        }
+
+        let (dr, dc) = direction.step();
+        let last_row = row as isize + dr * (length as isize - 1);
+        let last_column = column as isize + dc * (length as isize - 1);
+        if last_row < 0 || last_column < 0 ||
+           self.get(last_row as usize, last_column as usize).is_none() ||
+           self.get(row, column).is_none() {
+            return None;
+        }
+
+        let cells = (0..length as isize)
+            .map(|i| {
+                let r = (row as isize + dr * i) as usize;
+                let c = (column as isize + dc * i) as usize;
+                &self.cells[r * self.width + c]
+            })
+            .collect();
+        Some(cells)
+    }
+
+    /// Reduce every line of `length` cells in the grid (in all directions)
+    /// using `combine`, and return the line which gave the biggest result.
+    ///
+    /// Ties are resolved in favour of the line found first, scanning each
+    /// direction in the order given by `Direction::all()` and then row by
+    /// row.
+    pub fn best_line<V, F>(&self, length: usize, combine: F) -> Option<Line<V>>
+        where V: PartialOrd,
+              F: Fn(&[&T]) -> V
+    {
+        let mut best: Option<Line<V>> = None;
+
+        for &direction in Direction::all().iter() {
+            for row in 0..self.height {
+                for column in 0..self.width {
+                    let cells = match self.line(row, column, direction, length) {
+                        Some(cells) => cells,
+                        None => continue,
+                    };
+
+                    let value = combine(&cells);
+                    let is_better = match best {
+                        Some(ref b) => value > b.value,
+                        None => true,
+                    };
+
+                    if is_better {
+                        best = Some(Line {
+                            row: row,
+                            column: column,
+                            direction: direction,
+                            value: value,
+                        });
+                    }
+                }
+            }
+        }
+
+        best
+    }
+}
+
+impl<T> Grid<T>
+    where T: Copy + PartialOrd
+{
+    /// Find the line of `length` adjacent cells with the greatest product.
+    pub fn max_product(&self, length: usize) -> Option<Line<T>>
+        where T: One + Mul<Output = T>
+    {
+        self.best_line(length,
+                       |cells| cells.iter().fold(T::one(), |acc, &&cell| acc * cell))
+    }
+
+    /// Find the line of `length` adjacent cells with the greatest sum.
+    pub fn max_sum(&self, length: usize) -> Option<Line<T>>
+        where T: Zero + Add<Output = T>
+    {
+        self.best_line(length,
+                       |cells| cells.iter().fold(T::zero(), |acc, &&cell| acc + cell))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<usize> {
+        Grid::from_rows(vec![vec![1, 2, 3, 4],
+                             vec![5, 6, 7, 8],
+                             vec![9, 1, 2, 3],
+                             vec![4, 5, 6, 7]])
+            .unwrap()
+    }
+
+    #[test]
+    fn ragged_rows_are_rejected() {
+        let got = Grid::from_rows(vec![vec![1, 2], vec![3]]);
+        assert!(got.is_err());
+    }
+
+    #[test]
+    fn lines_must_fit_in_the_grid() {
+        let grid = sample();
+        assert!(grid.line(0, 2, Direction::Right, 3).is_none());
+        assert!(grid.line(0, 1, Direction::DownLeft, 3).is_none());
+        assert_eq!(grid.line(0, 2, Direction::DownLeft, 3),
+                   Some(vec![&3, &6, &9]));
+    }
+
+    #[test]
+    fn find_largest_product() {
+        let grid = sample();
+        let should_be = Line {
+            row: 1,
+            column: 1,
+            direction: Direction::Right,
+            value: 6 * 7 * 8,
+        };
+
+        let got = grid.max_product(3).unwrap();
+        assert_eq!(got, should_be);
+    }
+
+    #[test]
+    fn find_largest_diagonal_sum() {
+        let grid = Grid::from_rows(vec![vec![1, 1, 1, 9],
+                                        vec![1, 1, 9, 1],
+                                        vec![1, 9, 1, 1],
+                                        vec![9, 1, 1, 1]])
+            .unwrap();
+        let should_be = Line {
+            row: 0,
+            column: 3,
+            direction: Direction::DownLeft,
+            value: 36,
+        };
+
+        let got = grid.max_sum(4).unwrap();
+        assert_eq!(got, should_be);
+    }
+
+    #[test]
+    fn single_row_behaves_like_a_sliding_window() {
+        let grid = Grid::from_rows(vec![vec![3, 1, 4, 1, 5, 9, 2, 6]]).unwrap();
+        let got = grid.max_product(2).unwrap();
+        assert_eq!((got.column, got.value), (4, 45));
+    }
+
+    #[test]
+    fn line_too_long() {
+        let grid = sample();
+        assert!(grid.max_product(5).is_none());
+        assert!(grid.max_sum(0).is_none());
+    }
+}
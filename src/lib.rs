@@ -4,6 +4,7 @@
 
 #![feature(test)]
 #![feature(inclusive_range_syntax)]
+#![feature(i128_type)]
 
 // Add some more lints
 #![deny(missing_docs,
@@ -17,12 +18,23 @@
 extern crate log;
 extern crate test;
 extern crate regex;
+extern crate num;
+extern crate sha2;
+extern crate toml;
 
+pub mod data;
+pub mod solution;
 mod primes;
+mod grid;
+mod series;
 
 use std::cmp::{max, min};
+use std::io::{self, BufRead};
+use std::mem;
 
 pub use primes::{ErosthenesSeive, primes};
+pub use grid::{Direction, Grid, Line};
+pub use series::{WindowProduct, max_window_product};
 
 
 /// Using the frontier method, find the maximum path through a triangle
@@ -51,6 +63,58 @@ pub fn frontier_reduce(mut triangle: Vec<Vec<usize>>) -> usize {
 }
 
 
+/// Find the maximum path through a triangle, reading it one row at a time
+/// from `reader` and working from the top down.
+///
+/// Where `frontier_reduce()` needs the entire triangle in memory, this only
+/// ever keeps the best totals for the most recently read row. That makes it
+/// suitable for generated triangles with millions of rows. Each row should
+/// be on its own line with its values separated by whitespace, and blank
+/// lines are ignored.
+pub fn frontier_reduce_streaming<R: BufRead>(reader: R) -> io::Result<usize> {
+    let mut frontier: Vec<usize> = Vec::new();
+    let mut next = Vec::new();
+
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        next.clear();
+        for (j, word) in line.split_whitespace().enumerate() {
+            let value: usize = word.parse()
+                .map_err(|e| {
+                    io::Error::new(io::ErrorKind::InvalidData,
+                                   format!("Line {}: unable to parse {:?} ({})", i + 1, word, e))
+                })?;
+
+            // The only ways to get here are from directly above or above
+            // and to the left
+            let above_left = if j > 0 { frontier.get(j - 1) } else { None };
+            let above = frontier.get(j);
+            let best = max(above_left, above).cloned().unwrap_or(0);
+            next.push(value + best);
+        }
+
+        if next.len() != frontier.len() + 1 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                      format!("Line {}: expected {} values but found {}",
+                                              i + 1,
+                                              frontier.len() + 1,
+                                              next.len())));
+        }
+
+        mem::swap(&mut frontier, &mut next);
+        debug!("{:?}", frontier);
+    }
+
+    frontier.into_iter()
+        .max()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "The triangle was empty"))
+}
+
+
 /// An iterator over the fibonacci numbers.
 #[derive(Debug, Copy, Clone)]
 pub struct Fibonacci {
@@ -131,6 +195,8 @@ pub fn pythag_triples(n: u32) -> Vec<(u32, u32, u32)> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::fs::File;
+    use std::io::{BufReader, Read};
 
     #[test]
     fn frontier_reduce_using_sample() {
@@ -140,6 +206,36 @@ mod tests {
         assert_eq!(should_be, got);
     }
 
+    #[test]
+    fn streaming_frontier_reduce_using_sample() {
+        let triangle = "3\n7 4\n2 4 6\n8 5 9 3\n";
+        let got = frontier_reduce_streaming(triangle.as_bytes()).unwrap();
+        assert_eq!(got, 23);
+    }
+
+    #[test]
+    fn streaming_frontier_reduce_matches_frontier_reduce() {
+        let filename = concat!(env!("CARGO_MANIFEST_DIR"),
+                               "/data/problem_067_triangle.txt");
+        let mut contents = String::new();
+        File::open(filename).unwrap().read_to_string(&mut contents).unwrap();
+
+        let triangle = contents.lines()
+            .map(|line| line.split_whitespace().map(|n| n.parse().unwrap()).collect())
+            .collect();
+        let should_be = frontier_reduce(triangle);
+
+        let got = frontier_reduce_streaming(BufReader::new(File::open(filename).unwrap()));
+        assert_eq!(got.unwrap(), should_be);
+    }
+
+    #[test]
+    fn streaming_frontier_reduce_rejects_ragged_rows() {
+        let triangle = "3\n7 4\n2 4\n";
+        assert!(frontier_reduce_streaming(triangle.as_bytes()).is_err());
+        assert!(frontier_reduce_streaming("".as_bytes()).is_err());
+    }
+
     #[test]
     fn basic_fib() {
         let should_be = vec![1, 1, 2, 3, 5, 8, 13];
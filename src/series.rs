@@ -0,0 +1,176 @@
+//! Functions for working with long series of numbers.
+
+use num::BigUint;
+
+
+/// The window found by `max_window_product()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowProduct {
+    /// The index of the window's first element.
+    pub offset: usize,
+    /// The product of every element in the window.
+    pub product: BigUint,
+}
+
+
+/// Find the `length` adjacent values with the greatest product.
+///
+/// This runs in linear time by keeping a running product for the current
+/// window, multiplying in each new value and dividing out the one which fell
+/// off the end. Zeroes can't be divided out, so the window restarts just
+/// after each one. The running product is kept in a `u128` and only falls
+/// back to a `BigUint` if that would overflow.
+///
+/// Returns `None` if `length` is zero or longer than `values`. If every
+/// window contains a zero, the first window wins with a product of zero.
+pub fn max_window_product(values: &[u64], length: usize) -> Option<WindowProduct> {
+    if length == 0 || length > values.len() {
+        return None;
+    }
+
+    let (offset, product) = match scan::<u128>(values, length) {
+        Some((offset, product)) => {
+            let high = BigUint::from((product >> 64) as u64);
+            let low = BigUint::from(product as u64);
+            (offset, (high << 64) + low)
+        }
+        None => scan::<BigUint>(values, length).expect("A BigUint can't overflow"),
+    };
+
+    Some(WindowProduct {
+        offset: offset,
+        product: product,
+    })
+}
+
+
+/// A number which can be used to keep a running product.
+trait RunningProduct: Sized + Clone + PartialOrd {
+    fn zero() -> Self;
+    fn one() -> Self;
+    /// Multiply by `n`, returning `None` on overflow.
+    fn times(&self, n: u64) -> Option<Self>;
+    /// Divide by `n`, which is known to be an exact divisor.
+    fn divided_by(&self, n: u64) -> Self;
+}
+
+impl RunningProduct for u128 {
+    fn zero() -> u128 {
+        0
+    }
+
+    fn one() -> u128 {
+        1
+    }
+
+    fn times(&self, n: u64) -> Option<u128> {
+        self.checked_mul(n as u128)
+    }
+
+    fn divided_by(&self, n: u64) -> u128 {
+        self / n as u128
+    }
+}
+
+impl RunningProduct for BigUint {
+    fn zero() -> BigUint {
+        BigUint::from(0_u64)
+    }
+
+    fn one() -> BigUint {
+        BigUint::from(1_u64)
+    }
+
+    fn times(&self, n: u64) -> Option<BigUint> {
+        Some(self * BigUint::from(n))
+    }
+
+    fn divided_by(&self, n: u64) -> BigUint {
+        self / BigUint::from(n)
+    }
+}
+
+/// Slide a window over `values`, returning the offset and product of the
+/// best window or `None` if the running product overflowed.
+fn scan<P: RunningProduct>(values: &[u64], length: usize) -> Option<(usize, P)> {
+    let mut best = (0, P::zero());
+    let mut start = 0;
+    let mut product = P::one();
+
+    for (i, &value) in values.iter().enumerate() {
+        if value == 0 {
+            // No window containing this element can beat zero, so skip it
+            start = i + 1;
+            product = P::one();
+            continue;
+        }
+
+        if i - start == length {
+            product = product.divided_by(values[start]);
+            start += 1;
+        }
+
+        product = match product.times(value) {
+            Some(p) => p,
+            None => return None,
+        };
+
+        if i + 1 - start == length && product > best.1 {
+            best = (start, product.clone());
+        }
+    }
+
+    Some(best)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num;
+
+    #[test]
+    fn find_the_largest_window() {
+        let values = vec![3, 1, 4, 1, 5, 9, 2, 6, 5, 3];
+        let should_be = WindowProduct {
+            offset: 4,
+            product: BigUint::from(5 * 9 * 2 * 6_u64),
+        };
+
+        let got = max_window_product(&values, 4).unwrap();
+        assert_eq!(got, should_be);
+    }
+
+    #[test]
+    fn zeroes_reset_the_window() {
+        let values = vec![9, 9, 0, 1, 2, 3, 0, 8, 8];
+        let got = max_window_product(&values, 3).unwrap();
+        assert_eq!(got.offset, 3);
+        assert_eq!(got.product, BigUint::from(6_u64));
+    }
+
+    #[test]
+    fn every_window_contains_a_zero() {
+        let values = vec![1, 0, 2, 0, 3];
+        let got = max_window_product(&values, 2).unwrap();
+        assert_eq!(got.offset, 0);
+        assert_eq!(got.product, BigUint::from(0_u64));
+    }
+
+    #[test]
+    fn promote_to_a_biguint_on_overflow() {
+        let values = vec![9; 60];
+        let should_be = num::pow(BigUint::from(9_u64), 45);
+
+        let got = max_window_product(&values, 45).unwrap();
+        assert_eq!(got.offset, 0);
+        assert_eq!(got.product, should_be);
+    }
+
+    #[test]
+    fn invalid_window_lengths() {
+        let values = vec![1, 2, 3];
+        assert!(max_window_product(&values, 0).is_none());
+        assert!(max_window_product(&values, 4).is_none());
+    }
+}
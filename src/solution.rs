@@ -0,0 +1,264 @@
+//! A common interface implemented by every challenge, so solutions can be
+//! discovered and run without going through a separate binary for each one.
+
+use std::fmt::{self, Debug, Display, Formatter};
+use std::process;
+
+
+/// The exit code used by a challenge binary whose solution isn't finished
+/// yet, so the runner can tell it apart from one which crashed.
+pub const TODO_EXIT_CODE: i32 = 3;
+
+
+/// The answer to a problem.
+///
+/// Answers are stored as text because while most are numbers, some (like
+/// "the first ten digits of ...") are better thought of as strings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Answer(String);
+
+impl Answer {
+    /// Get the answer as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(other: String) -> Answer {
+        Answer(other)
+    }
+}
+
+impl<'a> From<&'a str> for Answer {
+    fn from(other: &'a str) -> Answer {
+        Answer(other.to_string())
+    }
+}
+
+macro_rules! answer_from_number {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Answer {
+                fn from(other: $ty) -> Answer {
+                    Answer(other.to_string())
+                }
+            }
+        )*
+    };
+}
+
+answer_from_number!(u32, u64, usize, i32, i64, isize);
+
+
+/// A solution to one of the Project Euler problems.
+///
+/// A problem may have several competing solutions (e.g. a naive one and a
+/// clever one), in which case each should be given its own `name()`.
+pub trait Solution: Sync {
+    /// The problem's number.
+    fn number(&self) -> usize;
+
+    /// The problem's title (e.g. "Multiples of 3 and 5").
+    fn title(&self) -> &str;
+
+    /// A short name used to tell this solution apart from any others for the
+    /// same problem.
+    fn name(&self) -> &str {
+        "default"
+    }
+
+    /// Is this solution finished?
+    ///
+    /// Work-in-progress solutions should return `false` so they get reported
+    /// as TODO rather than as failures.
+    fn is_implemented(&self) -> bool {
+        true
+    }
+
+    /// Work out the answer.
+    fn solve(&self) -> Answer;
+}
+
+
+/// A collection of solutions, ordered by problem number.
+///
+/// Solutions to the same problem are kept in the order they were registered,
+/// with the first one being used whenever a problem is looked up by number.
+#[derive(Default)]
+pub struct Registry {
+    solutions: Vec<Box<Solution>>,
+}
+
+impl Registry {
+    /// Create an empty registry.
+    pub fn new() -> Registry {
+        Registry::default()
+    }
+
+    /// Add a solution to the registry.
+    pub fn register<S: Solution + 'static>(&mut self, solution: S) {
+        let number = solution.number();
+        let index = self.solutions
+            .iter()
+            .position(|s| s.number() > number)
+            .unwrap_or(self.solutions.len());
+        self.solutions.insert(index, Box::new(solution));
+    }
+
+    /// Look up the (first) solution to a particular problem.
+    pub fn get(&self, number: usize) -> Option<&Solution> {
+        self.solutions
+            .iter()
+            .find(|s| s.number() == number)
+            .map(|s| &**s)
+    }
+
+    /// Get every solution registered for a particular problem.
+    pub fn implementations(&self, number: usize) -> Vec<&Solution> {
+        self.solutions
+            .iter()
+            .filter(|s| s.number() == number)
+            .map(|s| &**s)
+            .collect()
+    }
+
+    /// Every registered solution, ordered by problem number.
+    pub fn solutions(&self) -> &[Box<Solution>] {
+        &self.solutions
+    }
+
+    /// The first solution registered for each problem, ordered by problem
+    /// number.
+    pub fn problems(&self) -> Vec<&Solution> {
+        let mut problems: Vec<&Solution> = Vec::new();
+
+        for solution in &self.solutions {
+            if problems.last().map(|s| s.number()) != Some(solution.number()) {
+                problems.push(&**solution);
+            }
+        }
+
+        problems
+    }
+
+    /// The number of registered solutions.
+    pub fn len(&self) -> usize {
+        self.solutions.len()
+    }
+
+    /// Are there any solutions in the registry?
+    pub fn is_empty(&self) -> bool {
+        self.solutions.is_empty()
+    }
+}
+
+impl Debug for Registry {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let solutions: Vec<String> = self.solutions
+            .iter()
+            .map(|s| format!("{}/{}", s.number(), s.name()))
+            .collect();
+        f.debug_struct("Registry")
+            .field("solutions", &solutions)
+            .finish()
+    }
+}
+
+
+/// Solve a problem and print its answer, for use as a challenge binary's
+/// `main()`.
+///
+/// If the solution isn't implemented yet this exits with `TODO_EXIT_CODE`
+/// instead.
+pub fn run<S: Solution>(solution: &S) {
+    if !solution.is_implemented() {
+        eprintln!("Problem {} hasn't been solved yet", solution.number());
+        process::exit(TODO_EXIT_CODE);
+    }
+
+    println!("{}", solution.solve());
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Dummy(usize);
+
+    impl Solution for Dummy {
+        fn number(&self) -> usize {
+            self.0
+        }
+
+        fn title(&self) -> &str {
+            "Dummy"
+        }
+
+        fn solve(&self) -> Answer {
+            Answer::from(self.0 * 2)
+        }
+    }
+
+    #[test]
+    fn registry_is_sorted_by_number() {
+        let mut registry = Registry::new();
+        registry.register(Dummy(67));
+        registry.register(Dummy(1));
+        registry.register(Dummy(13));
+
+        let got: Vec<usize> = registry.solutions().iter().map(|s| s.number()).collect();
+        assert_eq!(got, vec![1, 13, 67]);
+    }
+
+    struct Other(usize);
+
+    impl Solution for Other {
+        fn number(&self) -> usize {
+            self.0
+        }
+
+        fn title(&self) -> &str {
+            "Dummy"
+        }
+
+        fn name(&self) -> &str {
+            "other"
+        }
+
+        fn solve(&self) -> Answer {
+            Answer::from(self.0 + self.0)
+        }
+    }
+
+    #[test]
+    fn multiple_implementations_of_a_problem() {
+        let mut registry = Registry::new();
+        registry.register(Dummy(4));
+        registry.register(Dummy(1));
+        registry.register(Other(4));
+
+        let names: Vec<&str> = registry.implementations(4).iter().map(|s| s.name()).collect();
+        assert_eq!(names, vec!["default", "other"]);
+
+        assert_eq!(registry.get(4).unwrap().name(), "default");
+        let problems: Vec<usize> = registry.problems().iter().map(|s| s.number()).collect();
+        assert_eq!(problems, vec![1, 4]);
+    }
+
+    #[test]
+    fn look_up_a_solution() {
+        let mut registry = Registry::new();
+        registry.register(Dummy(13));
+
+        assert_eq!(registry.get(13).unwrap().solve(), Answer::from("26"));
+        assert!(registry.get(14).is_none());
+    }
+}